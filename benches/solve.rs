@@ -0,0 +1,81 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use ccelm::{
+    ConcurrentSolver, DatasetMetadata, DatasetReader, FindG, FindS, Solver, TrainingExample,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Counts allocations, so that how much a solve allocates can be reported alongside its time
+struct CountingAllocator;
+
+static N_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        N_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// How many training examples of each dataset to learn from, enough for the general boundary to
+/// grow to thousands of hypotheses while keeping each iteration around a second
+const N_EXAMPLES: usize = 40;
+
+fn load(dataset: &str) -> (Vec<TrainingExample>, Arc<DatasetMetadata>) {
+    let directory = format!("./data/{dataset}");
+    let metadata: DatasetMetadata =
+        serde_yaml::from_reader(std::fs::File::open(format!("{directory}/metadata.yaml")).unwrap())
+            .unwrap();
+
+    let training_examples =
+        DatasetReader::new(format!("{directory}/dataset.tsv"), metadata.clone())
+            .unwrap()
+            .take(N_EXAMPLES)
+            .collect::<Result<Vec<TrainingExample>, _>>()
+            .unwrap();
+
+    (training_examples, Arc::new(metadata))
+}
+
+fn bench_solvers(c: &mut Criterion) {
+    for dataset in ["boundaries-germanic", "boundaries-indo-european"] {
+        let (training_examples, metadata) = load(dataset);
+
+        let before = N_ALLOCATIONS.load(Ordering::Relaxed);
+        Solver::new(training_examples.clone(), &metadata).solve();
+        eprintln!(
+            "{dataset}/solver: {} allocations",
+            N_ALLOCATIONS.load(Ordering::Relaxed) - before
+        );
+
+        let mut group = c.benchmark_group(dataset);
+        group.sample_size(10);
+        group.bench_function("solver", |b| {
+            b.iter(|| Solver::new(training_examples.clone(), &metadata).solve())
+        });
+        group.bench_function("concurrent", |b| {
+            b.iter(|| ConcurrentSolver::new(training_examples.clone(), &metadata, 4).solve())
+        });
+        // Only learning the specific boundary, or one general hypothesis, skips maintaining the
+        // general boundary that dominates the time of the solvers above
+        group.bench_function("find_s", |b| {
+            b.iter(|| FindS::new(training_examples.clone(), &metadata).solve())
+        });
+        group.bench_function("find_g", |b| {
+            b.iter(|| FindG::new(training_examples.clone(), &metadata).solve())
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_solvers);
+criterion_main!(benches);
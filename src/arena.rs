@@ -0,0 +1,229 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::{Attribute, TrainingExample};
+
+/// Identifies a hypothesis interned in a [`HypothesisArena`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HypothesisId(u32);
+
+/// Interns the attribute chains of hypotheses into one flat buffer, so each hypothesis is a
+/// compact [`HypothesisId`] rather than an allocation of its own. Interning a chain that is
+/// already stored returns the id it was first given, so duplicates collapse as they are added.
+///
+/// The solvers intern the members of the general boundary that a negative training example has to
+/// specialize, and their specializations, into arenas. The many duplicate specializations that
+/// different members produce are then dropped before they ever reach the general boundary.
+#[derive(Clone, Debug)]
+pub struct HypothesisArena {
+    n_attributes: usize,
+    attributes: Vec<Attribute>,
+    len: usize,
+    /// An open addressing hash table of the ids, where `EMPTY` marks a free slot
+    slots: Vec<u32>,
+}
+
+const EMPTY: u32 = u32::MAX;
+
+fn hash(attributes: &[Attribute]) -> usize {
+    let mut hasher = DefaultHasher::new();
+    attributes.hash(&mut hasher);
+    hasher.finish() as usize
+}
+
+impl HypothesisArena {
+    pub fn new(n_attributes: usize) -> Self {
+        Self {
+            n_attributes,
+            attributes: vec![],
+            len: 0,
+            slots: vec![EMPTY; 16],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove every hypothesis while keeping the storage around for reuse. Ids handed out before
+    /// are no longer valid.
+    pub fn clear(&mut self) {
+        self.attributes.clear();
+        self.len = 0;
+        self.slots.fill(EMPTY);
+    }
+
+    pub fn get(&self, id: HypothesisId) -> &[Attribute] {
+        let start = id.0 as usize * self.n_attributes;
+        &self.attributes[start..start + self.n_attributes]
+    }
+
+    /// The ids of every hypothesis, in the order they were interned
+    pub fn ids(&self) -> impl Iterator<Item = HypothesisId> {
+        (0..self.len).map(|id| HypothesisId(id as u32))
+    }
+
+    /// Add a hypothesis unless it is already stored. Returns its id and whether it was added.
+    pub fn intern(&mut self, attributes: &[Attribute]) -> (HypothesisId, bool) {
+        debug_assert_eq!(attributes.len(), self.n_attributes);
+
+        self.attributes.extend_from_slice(attributes);
+        self.intern_last()
+    }
+
+    /// Intern every hypothesis in `other` that isn't already stored
+    pub fn extend_from(&mut self, other: &Self) {
+        debug_assert_eq!(other.n_attributes, self.n_attributes);
+
+        for id in other.ids() {
+            self.intern(other.get(id));
+        }
+    }
+
+    /// Intern the minimal specializations of `hypothesis` that classify `training_example` as
+    /// negative, as per [`crate::Hypothesis::specialize`], skipping those for which `keep` returns
+    /// false. Each specialization is written straight into the arena rather than cloning
+    /// `hypothesis` first.
+    pub fn specialize<F>(
+        &mut self,
+        hypothesis: &[Attribute],
+        training_example: &TrainingExample,
+        value_data: &[Vec<String>],
+        keep: F,
+    ) where
+        F: Fn(&[Attribute]) -> bool,
+    {
+        debug_assert_eq!(hypothesis.len(), self.n_attributes);
+
+        for (index, (attribute, other_attribute)) in hypothesis
+            .iter()
+            .zip(training_example.attributes.iter())
+            .enumerate()
+        {
+            for specialization in attribute.specializations(other_attribute, &value_data[index]) {
+                let start = self.attributes.len();
+                self.attributes.extend_from_slice(hypothesis);
+                self.attributes[start + index] = specialization;
+
+                if keep(&self.attributes[start..]) {
+                    self.intern_last();
+                } else {
+                    self.attributes.truncate(start);
+                }
+            }
+        }
+    }
+
+    /// Intern the chain at the end of the buffer, dropping it again if it is a duplicate
+    fn intern_last(&mut self) -> (HypothesisId, bool) {
+        let start = self.len * self.n_attributes;
+        let mask = self.slots.len() - 1;
+        let mut slot = hash(&self.attributes[start..]) & mask;
+        while self.slots[slot] != EMPTY {
+            let id = HypothesisId(self.slots[slot]);
+            if self.get(id) == &self.attributes[start..] {
+                self.attributes.truncate(start);
+                return (id, false);
+            }
+            slot = (slot + 1) & mask;
+        }
+
+        let id = u32::try_from(self.len)
+            .ok()
+            .filter(|id| *id != EMPTY)
+            .expect("An arena can hold fewer than 2^32 - 1 hypotheses");
+        self.slots[slot] = id;
+        self.len += 1;
+
+        // Keep the table at most half full so probe sequences stay short
+        if self.len * 2 > self.slots.len() {
+            self.grow();
+        }
+
+        (HypothesisId(id), true)
+    }
+
+    fn grow(&mut self) {
+        let mut slots = vec![EMPTY; self.slots.len() * 2];
+        let mask = slots.len() - 1;
+        for id in self.ids() {
+            let mut slot = hash(self.get(id)) & mask;
+            while slots[slot] != EMPTY {
+                slot = (slot + 1) & mask;
+            }
+            slots[slot] = id.0;
+        }
+        self.slots = slots;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::DATASET_METADATA;
+    use crate::Hypothesis;
+
+    fn hypothesis(record: &str) -> Hypothesis {
+        Hypothesis::from_str(record, &DATASET_METADATA).unwrap()
+    }
+
+    #[test]
+    fn test_intern_deduplicates() {
+        let mut arena = HypothesisArena::new(6);
+        let records = (0..100).map(|index| match index % 3 {
+            0 => "Sunny,?,?,?,?,?",
+            1 => "?,Warm,?,?,?,?",
+            _ => "?,?,?,?,?,Same",
+        });
+
+        let ids = records
+            .map(|record| arena.intern(&hypothesis(record).attributes).0)
+            .collect::<Vec<HypothesisId>>();
+
+        assert_eq!(arena.len(), 3);
+        assert_eq!(ids[..4], [ids[0], ids[1], ids[2], ids[0]]);
+        assert_eq!(arena.get(ids[1]), hypothesis("?,Warm,?,?,?,?").attributes);
+        assert_eq!(
+            arena.intern(&hypothesis("?,Warm,?,?,?,?").attributes),
+            (ids[1], false)
+        );
+    }
+
+    #[test]
+    fn test_specialize_matches_hypothesis() {
+        let general = hypothesis("?,?,?,?,?,?");
+        let specific = hypothesis("Sunny,Warm,?,Strong,Warm,Same");
+        let example = TrainingExample::from_str(
+            "Rainy,Cold,High,Strong,Warm,Change,false",
+            &DATASET_METADATA,
+        )
+        .unwrap();
+
+        let expected = general
+            .specialize(&example, &DATASET_METADATA.columns)
+            .into_iter()
+            .filter(|specialization| specialization.is_more_general(&specific))
+            .map(|specialization| specialization.attributes)
+            .collect::<Vec<Vec<Attribute>>>();
+
+        let mut arena = HypothesisArena::new(6);
+        for _ in 0..2 {
+            arena.specialize(
+                &general.attributes,
+                &example,
+                &DATASET_METADATA.columns,
+                |attributes| specific.is_more_specific_than(attributes),
+            );
+        }
+
+        let actual = arena
+            .ids()
+            .map(|id| arena.get(id).to_vec())
+            .collect::<Vec<Vec<Attribute>>>();
+        assert_eq!(actual, expected);
+    }
+}
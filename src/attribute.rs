@@ -1,10 +1,12 @@
 use std::fmt::Display;
 
+use serde::{Deserialize, Serialize};
+
 use crate::reader::DatasetMetadata;
 
 /// Deriving `PartialOrd` works by ranking enums in the order they are defined (Any is ranked the
 /// highest - i.e. it is the most general)
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Attribute {
     /// No value can yield a positive example
     NoValue,
@@ -67,6 +69,20 @@ impl Attribute {
         }
     }
 
+    /// Render this attribute as it would appear in the dataset, i.e. using the metadata's value
+    /// names and wildcard strings
+    pub fn to_dataset_string(
+        &self,
+        column_index: usize,
+        dataset_metadata: &DatasetMetadata,
+    ) -> String {
+        match self {
+            Self::NoValue => dataset_metadata.no_value_string.clone(),
+            Self::Any => dataset_metadata.any_value_string.clone(),
+            Self::Value(_) => self.to_valued_string(column_index, dataset_metadata),
+        }
+    }
+
     pub fn is_consistent(&self, other: &Self) -> bool {
         if let (Attribute::Value(left), Attribute::Value(right)) = (self, other) {
             return left == right;
@@ -74,6 +90,20 @@ impl Attribute {
         self >= other
     }
 
+    /// The most specific attribute that is at least as general as both attributes
+    pub fn join(&self, other: &Self) -> Self {
+        self.generalize(other).unwrap_or_else(|| self.clone())
+    }
+
+    /// The most general attribute that is at least as specific as both attributes
+    pub fn meet(&self, other: &Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a.clone(),
+            (Attribute::Any, other) | (other, Attribute::Any) => other.clone(),
+            _ => Attribute::NoValue,
+        }
+    }
+
     /// Return the most specific attribute that satisfies other whilst being the smallest
     /// generalization of self.
     ///
@@ -102,28 +132,37 @@ impl Attribute {
     /// Some attribute if the attribute can be further specialized by the provided attribute. Else,
     /// none
     pub fn specialize(&self, other: &Self, possible_values: &[String]) -> Option<Vec<Self>> {
-        match (self, other) {
-            (a, b) if a == b => None,
-            (Attribute::NoValue, _) => None, // NoValue cannot be specialized further
-            (Attribute::Any, Attribute::NoValue) => Some(vec![Attribute::NoValue]),
+        let specializations: Vec<Self> = self.specializations(other, possible_values).collect();
+        (!specializations.is_empty()).then_some(specializations)
+    }
+
+    /// The attributes returned by [`Attribute::specialize`], without collecting them into a
+    /// vector. Yields nothing if the attribute can't be specialized by the provided attribute.
+    pub fn specializations(
+        &self,
+        other: &Self,
+        possible_values: &[String],
+    ) -> impl Iterator<Item = Self> {
+        let n_values = possible_values.len();
+        let (n_candidates, excluded, is_no_value) = match (self, other) {
+            // A missing value is only covered by any value, so every single value excludes it. If
+            // there are no values, only no value at all is left
+            (Attribute::Any, Attribute::Any) => (n_values, None, n_values == 0),
+            (a, b) if a == b => (0, None, false),
             (Attribute::Any, Attribute::Value(v)) => {
-                let values: Vec<Attribute> = (0..possible_values.len())
-                    .map(|index| u8::try_from(index).unwrap())
-                    .filter(|index| index != v)
-                    .map(Attribute::Value)
-                    .collect();
-
-                if values.is_empty() {
-                    Some(vec![Attribute::NoValue])
-                } else {
-                    Some(values)
-                }
+                // If there are no other values, only no value at all is left
+                let has_other_values = (0..n_values).any(|index| index != usize::from(*v));
+                (n_values, Some(*v), !has_other_values)
             }
-            (Attribute::Value(_), Attribute::NoValue) => Some(vec![Attribute::NoValue]),
-            (Attribute::Value(_), Attribute::Any) => None,
-            (Attribute::Value(_), Attribute::Value(_)) => None,
-            _ => unreachable!(),
-        }
+            (Attribute::Any | Attribute::Value(_), Attribute::NoValue) => (0, None, true),
+            _ => (0, None, false),
+        };
+
+        let values = (0..n_candidates)
+            .map(|index| u8::try_from(index).unwrap())
+            .filter(move |index| Some(*index) != excluded)
+            .map(Attribute::Value);
+        values.chain(is_no_value.then_some(Attribute::NoValue))
     }
 }
 
@@ -158,10 +197,48 @@ mod tests {
         assert!(!value.is_consistent(&other_value));
     }
 
+    #[test]
+    fn test_attribute_meet_and_join() {
+        assert_eq!(
+            Attribute::Any.meet(&Attribute::Value(1)),
+            Attribute::Value(1)
+        );
+        assert_eq!(
+            Attribute::Value(0).meet(&Attribute::Value(1)),
+            Attribute::NoValue
+        );
+        assert_eq!(
+            Attribute::NoValue.join(&Attribute::Value(1)),
+            Attribute::Value(1)
+        );
+        assert_eq!(
+            Attribute::Value(0).join(&Attribute::Value(1)),
+            Attribute::Any
+        );
+    }
+
     #[test]
     fn test_is_consistent_value_reflexive() {
         let value = Attribute::Value(1);
 
         assert!(value.is_consistent(&value));
     }
+
+    #[test]
+    fn test_specialize_any_against_missing_value() {
+        let possible_values = ["Foo".to_string(), "Bar".to_string()];
+
+        assert_eq!(
+            Attribute::Any.specialize(&Attribute::Any, &possible_values),
+            Some(vec![Attribute::Value(0), Attribute::Value(1)])
+        );
+        assert_eq!(
+            Attribute::Any.specialize(&Attribute::Any, &[]),
+            Some(vec![Attribute::NoValue])
+        );
+        assert_eq!(
+            Attribute::Value(0).specialize(&Attribute::Any, &possible_values),
+            None
+        );
+    }
 }
@@ -0,0 +1,39 @@
+use serde::Serialize;
+
+use crate::{BudgetExceeded, Hypothesis};
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ComputedBoundaries {
+    pub specific_boundary: Option<Hypothesis>,
+    pub general_boundary: Vec<Hypothesis>,
+
+    /// Set when a solver was interrupted before it processed every training example, to how many
+    /// training examples it did process. The boundaries are then those of only these examples.
+    pub interrupted_after: Option<usize>,
+
+    /// Set when a solver aborted because its [`crate::Budget`] was exceeded. The boundaries are
+    /// then those of only the training examples processed up to that point.
+    pub budget_exceeded: Option<BudgetExceeded>,
+
+    /// Whether hypotheses were dropped from the general boundary to stay within a budget, so
+    /// that it no longer encloses every hypothesis consistent with the training examples
+    pub is_approximate: bool,
+}
+
+impl ComputedBoundaries {
+    /// Put the general boundary in its canonical order: sorted by [`Hypothesis`]'s `Ord`, with
+    /// each hypothesis once. Every solver returns boundaries in this order, so results don't
+    /// depend on how many threads were used and can be diffed from one run to the next.
+    pub fn canonicalize(&mut self) {
+        self.general_boundary.sort();
+        self.general_boundary.dedup();
+    }
+
+    /// Whether the general boundary is in its canonical order, see
+    /// [`ComputedBoundaries::canonicalize`]
+    pub fn is_canonical(&self) -> bool {
+        self.general_boundary
+            .windows(2)
+            .all(|pair| pair[0] < pair[1])
+    }
+}
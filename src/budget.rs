@@ -0,0 +1,254 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{Attribute, Hypothesis, HypothesisSpace};
+
+/// Limits on how far a solver may let the general boundary grow. Unlimited by default.
+///
+/// Once `max_general` or `memory_limit` is exceeded, a solver either aborts or, if a beam
+/// heuristic is set, keeps only the most promising general hypotheses and carries on with an
+/// approximate general boundary. Exceeding `time_limit` always aborts, since a smaller general
+/// boundary can't give back the time already spent.
+#[derive(Clone, Debug, Default)]
+pub struct Budget {
+    /// The largest general boundary allowed
+    pub max_general: Option<usize>,
+
+    /// How long a solve may take
+    pub time_limit: Option<Duration>,
+
+    /// How many bytes the general boundary may take up, as estimated from its size
+    pub memory_limit: Option<usize>,
+
+    /// How to rank general hypotheses when narrowing the general boundary to a beam, rather
+    /// than aborting
+    pub beam: Option<BeamHeuristic>,
+}
+
+/// Ranks general hypotheses by how promising they are to keep in a beam
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum BeamHeuristic {
+    /// Prefer hypotheses with the most attributes that accept any value
+    MostGeneral,
+
+    /// Prefer hypotheses with the fewest attributes that accept any value, i.e. those closest to
+    /// the specific boundary
+    MostSpecific,
+}
+
+/// Which limit of a [`Budget`] was exceeded
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum Limit {
+    MaxGeneral(usize),
+    Time(Duration),
+    Memory(usize),
+}
+
+/// How far a solver got before aborting because its [`Budget`] was exceeded
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BudgetExceeded {
+    pub limit: Limit,
+
+    /// How many training examples had been processed, the last of which exceeded the budget
+    pub n_examples: usize,
+
+    /// How many hypotheses the general boundary held at that point
+    pub general_boundary_len: usize,
+}
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let limit = match self.limit {
+            Limit::MaxGeneral(max_general) => {
+                format!("the limit of {max_general} general hypotheses")
+            }
+            Limit::Time(time_limit) => format!("the time limit of {time_limit:?}"),
+            Limit::Memory(memory_limit) => format!("the memory limit of {memory_limit} bytes"),
+        };
+
+        write!(
+            f,
+            "Exceeded {limit} after {} training examples, with {} general hypotheses",
+            self.n_examples, self.general_boundary_len
+        )
+    }
+}
+
+impl Budget {
+    /// Whether no limit is set
+    pub fn is_unlimited(&self) -> bool {
+        self.max_general.is_none() && self.time_limit.is_none() && self.memory_limit.is_none()
+    }
+
+    /// The estimated number of bytes taken up by one hypothesis in the general boundary
+    fn hypothesis_size(n_attributes: usize) -> usize {
+        std::mem::size_of::<Hypothesis>() + n_attributes * std::mem::size_of::<Attribute>()
+    }
+
+    /// The largest general boundary allowed by both the size and memory limits
+    fn max_general_len(&self, n_attributes: usize) -> Option<usize> {
+        let max_memory_len = self
+            .memory_limit
+            .map(|memory_limit| memory_limit / Self::hypothesis_size(n_attributes));
+
+        match (self.max_general, max_memory_len) {
+            (Some(max_general), Some(max_memory_len)) => Some(max_general.min(max_memory_len)),
+            (max_general, max_memory_len) => max_general.or(max_memory_len),
+        }
+    }
+
+    /// Check a general boundary of `general_boundary_len` hypotheses against the budget after
+    /// `n_examples` training examples of a solve that started at `started` have been processed
+    pub fn check(
+        &self,
+        started: Instant,
+        n_examples: usize,
+        general_boundary_len: usize,
+        n_attributes: usize,
+    ) -> Option<BudgetExceeded> {
+        let exceeded = |limit| BudgetExceeded {
+            limit,
+            n_examples,
+            general_boundary_len,
+        };
+
+        if let Some(time_limit) = self.time_limit {
+            if started.elapsed() > time_limit {
+                return Some(exceeded(Limit::Time(time_limit)));
+            }
+        }
+
+        let max_len = self.max_general_len(n_attributes)?;
+        if general_boundary_len <= max_len {
+            return None;
+        }
+
+        let limit = match self.max_general {
+            Some(max_general) if general_boundary_len > max_general => {
+                Limit::MaxGeneral(max_general)
+            }
+            _ => Limit::Memory(self.memory_limit.unwrap()),
+        };
+
+        Some(exceeded(limit))
+    }
+
+    /// Check the general boundary against the budget after a training example has been
+    /// processed, narrowing it to a beam if one is set. Returns whether any hypotheses were
+    /// dropped, in which case the general boundary may only be approximate.
+    pub fn enforce(
+        &self,
+        started: Instant,
+        n_examples: usize,
+        general_boundary: &mut HypothesisSpace,
+    ) -> Result<bool, BudgetExceeded> {
+        let n_attributes = general_boundary
+            .iter()
+            .next()
+            .map_or(0, |hypothesis| hypothesis.attributes.len());
+
+        let Some(exceeded) = self.check(started, n_examples, general_boundary.len(), n_attributes)
+        else {
+            return Ok(false);
+        };
+
+        let Some(heuristic) = self.beam else {
+            return Err(exceeded);
+        };
+        if let Limit::Time(_) = exceeded.limit {
+            return Err(exceeded);
+        }
+
+        let mut hypotheses = general_boundary.to_vec();
+        heuristic.narrow(&mut hypotheses, self.max_general_len(n_attributes).unwrap());
+        general_boundary.clear();
+        general_boundary.extend(hypotheses);
+
+        Ok(true)
+    }
+}
+
+impl BeamHeuristic {
+    /// Keep only the `width` most promising hypotheses. Ties keep their original order.
+    pub fn narrow(&self, general_boundary: &mut Vec<Hypothesis>, width: usize) {
+        let n_any = |hypothesis: &Hypothesis| {
+            hypothesis
+                .attributes
+                .iter()
+                .filter(|attribute| **attribute == Attribute::Any)
+                .count()
+        };
+
+        match self {
+            BeamHeuristic::MostGeneral => {
+                general_boundary.sort_by_key(|hypothesis| std::cmp::Reverse(n_any(hypothesis)))
+            }
+            BeamHeuristic::MostSpecific => general_boundary.sort_by_key(n_any),
+        }
+        general_boundary.truncate(width);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::Solver;
+
+    #[test]
+    fn test_abort_when_general_boundary_too_large() {
+        let mut solver = Solver::new(enjoysport_examples(), &DATASET_METADATA);
+        solver.budget.max_general = Some(1);
+        let boundaries = solver.solve();
+
+        let exceeded = boundaries.budget_exceeded.unwrap();
+        assert_eq!(exceeded.limit, Limit::MaxGeneral(1));
+        assert_eq!(exceeded.n_examples, 3);
+        assert!(!boundaries.is_approximate);
+    }
+
+    #[test]
+    fn test_beam_keeps_most_promising_hypotheses() {
+        let full = Solver::new(enjoysport_examples(), &DATASET_METADATA).solve();
+
+        let mut solver = Solver::new(enjoysport_examples(), &DATASET_METADATA);
+        solver.budget.max_general = Some(1);
+        solver.budget.beam = Some(BeamHeuristic::MostGeneral);
+        let boundaries = solver.solve();
+
+        assert!(boundaries.budget_exceeded.is_none());
+        assert!(boundaries.is_approximate);
+        assert_eq!(boundaries.specific_boundary, full.specific_boundary);
+        assert_eq!(boundaries.general_boundary.len(), 1);
+        assert!(full
+            .general_boundary
+            .iter()
+            .any(|general| general.is_more_general(&boundaries.general_boundary[0])));
+    }
+
+    #[test]
+    fn test_memory_limit_bounds_beam_width() {
+        let budget = Budget {
+            memory_limit: Some(2 * Budget::hypothesis_size(6)),
+            beam: Some(BeamHeuristic::MostSpecific),
+            ..Default::default()
+        };
+
+        let mut general_boundary = HypothesisSpace::new(&DATASET_METADATA);
+        general_boundary.extend([
+            Hypothesis::from_str("Sunny,?,?,?,?,?", &DATASET_METADATA).unwrap(),
+            Hypothesis::from_str("?,Warm,?,?,?,?", &DATASET_METADATA).unwrap(),
+            Hypothesis::from_str("?,?,Normal,Strong,?,?", &DATASET_METADATA).unwrap(),
+        ]);
+
+        assert!(budget
+            .enforce(Instant::now(), 1, &mut general_boundary)
+            .unwrap());
+        assert_eq!(general_boundary.len(), 2);
+        assert!(general_boundary
+            .contains(&Hypothesis::from_str("?,?,Normal,Strong,?,?", &DATASET_METADATA).unwrap()));
+    }
+}
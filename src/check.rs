@@ -0,0 +1,190 @@
+use std::fmt::Display;
+
+use crate::{ComputedBoundaries, Hypothesis, SubsumptionIndex, TrainingExample};
+
+/// A way in which a set of [`ComputedBoundaries`] fails [`ComputedBoundaries::verify`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// A member of the specific or general boundary misclassifies a training example
+    Inconsistent {
+        hypothesis: Hypothesis,
+        is_specific: bool,
+        /// The position of the training example in the examples checked against, from 0
+        example_index: usize,
+        training_example: TrainingExample,
+    },
+
+    /// The specific boundary isn't more specific than a member of the general boundary
+    NotMoreSpecific {
+        specific: Hypothesis,
+        general: Hypothesis,
+    },
+
+    /// A member of the general boundary is more general than or equal to another one
+    NotAntichain {
+        general: Hypothesis,
+        more_general: Hypothesis,
+    },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::Inconsistent {
+                hypothesis,
+                is_specific,
+                example_index,
+                training_example,
+            } => write!(
+                f,
+                "The {} hypothesis {hypothesis} misclassifies training example {}: {}",
+                if *is_specific { "specific" } else { "general" },
+                example_index + 1,
+                // Show the example as the row it was read from, so it can be found in the dataset
+                training_example
+                    .to_dataset_vec(&hypothesis.dataset_metadata)
+                    .join(&hypothesis.dataset_metadata.delimiter.to_string())
+            ),
+            Violation::NotMoreSpecific { specific, general } => write!(
+                f,
+                "The specific hypothesis {specific} isn't more specific than the general hypothesis {general}"
+            ),
+            Violation::NotAntichain {
+                general,
+                more_general,
+            } => write!(
+                f,
+                "The general hypothesis {general} is subsumed by the general hypothesis {more_general}"
+            ),
+        }
+    }
+}
+
+impl ComputedBoundaries {
+    /// Check that the boundaries are those of a version space of `training_examples`, returning
+    /// every violation found. The boundaries are valid if none are.
+    ///
+    /// Every member of both boundaries must classify each training example as it is labelled, the
+    /// specific boundary must be more specific than every member of the general boundary, and no
+    /// member of the general boundary may be more general than another.
+    ///
+    /// This doesn't check that the general boundary is complete, i.e. that it holds every maximally
+    /// general consistent hypothesis, which is what an approximate general boundary gives up.
+    pub fn verify(&self, training_examples: &[TrainingExample]) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        let members = self
+            .specific_boundary
+            .iter()
+            .map(|hypothesis| (hypothesis, true))
+            .chain(
+                self.general_boundary
+                    .iter()
+                    .map(|hypothesis| (hypothesis, false)),
+            );
+        for (hypothesis, is_specific) in members {
+            violations.extend(
+                training_examples
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, training_example)| !hypothesis.is_consistent(training_example))
+                    .map(
+                        |(example_index, training_example)| Violation::Inconsistent {
+                            hypothesis: hypothesis.clone(),
+                            is_specific,
+                            example_index,
+                            training_example: training_example.clone(),
+                        },
+                    ),
+            );
+        }
+
+        if let Some(specific) = &self.specific_boundary {
+            violations.extend(
+                self.general_boundary
+                    .iter()
+                    .filter(|general| !specific.is_more_specific(general))
+                    .map(|general| Violation::NotMoreSpecific {
+                        specific: specific.clone(),
+                        general: general.clone(),
+                    }),
+            );
+        }
+
+        let index = SubsumptionIndex::new(&self.general_boundary);
+        for (position, general) in self.general_boundary.iter().enumerate() {
+            violations.extend(
+                index
+                    .more_general(general)
+                    .filter(|other| *other != position)
+                    .map(|other| Violation::NotAntichain {
+                        general: general.clone(),
+                        more_general: self.general_boundary[other].clone(),
+                    }),
+            );
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::Solver;
+
+    fn hypothesis(record: &str) -> Hypothesis {
+        Hypothesis::from_str(record, &DATASET_METADATA).unwrap()
+    }
+
+    #[test]
+    fn test_learned_boundaries_pass() {
+        let training_examples = enjoysport_examples();
+        let boundaries = Solver::new(training_examples.clone(), &DATASET_METADATA).solve();
+
+        assert_eq!(boundaries.verify(&training_examples), vec![]);
+    }
+
+    #[test]
+    fn test_violations_are_reported() {
+        let training_examples = enjoysport_examples();
+        let mut boundaries = Solver::new(training_examples.clone(), &DATASET_METADATA).solve();
+
+        // ⟨Sunny, ?, ?, ?, ?, ?⟩ is already in the general boundary
+        boundaries
+            .general_boundary
+            .push(hypothesis("Sunny,?,?,Strong,?,?"));
+        let violations = boundaries.verify(&training_examples);
+        assert_eq!(
+            violations,
+            vec![Violation::NotAntichain {
+                general: hypothesis("Sunny,?,?,Strong,?,?"),
+                more_general: hypothesis("Sunny,?,?,?,?,?"),
+            }]
+        );
+
+        // The third training example is the only negative one
+        boundaries.general_boundary = vec![hypothesis("?,?,?,?,?,?")];
+        let violations = boundaries.verify(&training_examples);
+        assert_eq!(
+            violations,
+            vec![Violation::Inconsistent {
+                hypothesis: hypothesis("?,?,?,?,?,?"),
+                is_specific: false,
+                example_index: 2,
+                training_example: training_examples[2].clone(),
+            }]
+        );
+
+        boundaries.general_boundary = vec![hypothesis("Rainy,?,?,?,?,?")];
+        let violations = boundaries.verify(&training_examples);
+        assert!(violations.contains(&Violation::NotMoreSpecific {
+            specific: hypothesis("Sunny,Warm,?,Strong,?,?"),
+            general: hypothesis("Rainy,?,?,?,?,?"),
+        }));
+        assert!(violations
+            .iter()
+            .all(|violation| !matches!(violation, Violation::NotAntichain { .. })));
+    }
+}
@@ -0,0 +1,165 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use crate::model::canonical_attributes;
+use crate::reader::DatasetMetadata;
+use crate::{DatasetFingerprint, Hypothesis, Model, TrainingExample, MODEL_FORMAT_VERSION};
+
+/// Periodically saves the state of a solve to disk so that it can be continued after a crash.
+///
+/// Checkpoints are written as model files whose fingerprint covers the training examples processed
+/// so far, so `fingerprint.n_examples` is the index of the next training example to process. Unlike
+/// a finished model, the general boundary is saved as is, including any hypotheses that aren't
+/// maximally general, so that continuing from a checkpoint gives identical results.
+#[derive(Clone, Debug)]
+pub struct Checkpointer {
+    path: PathBuf,
+    every: usize,
+    fingerprint: DatasetFingerprint,
+}
+
+impl Checkpointer {
+    /// Save a checkpoint to `path` after every `every` training examples
+    pub fn new<P: Into<PathBuf>>(path: P, every: usize) -> Self {
+        Self::resuming(path, every, DatasetFingerprint::new())
+    }
+
+    /// Continue checkpointing a solve that has already processed the training examples with the
+    /// given fingerprint, e.g. one resumed from an earlier checkpoint
+    pub fn resuming<P: Into<PathBuf>>(
+        path: P,
+        every: usize,
+        fingerprint: DatasetFingerprint,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            every: every.max(1),
+            fingerprint,
+        }
+    }
+
+    /// Record that a training example is being processed. Returns whether a checkpoint should be
+    /// saved once it has been.
+    pub fn record(&mut self, example: &TrainingExample) -> bool {
+        self.fingerprint.update(example);
+        self.fingerprint.n_examples.is_multiple_of(self.every)
+    }
+
+    /// Save the boundaries as they are after the recorded training examples. The checkpoint is
+    /// written next to its destination first and then moved into place, so a crash while saving
+    /// leaves the previous checkpoint intact.
+    pub fn save(
+        &self,
+        specific_boundary: &Hypothesis,
+        general_boundary: &[Hypothesis],
+        is_approximate: bool,
+        metadata: &DatasetMetadata,
+    ) -> Result<(), Box<dyn Error>> {
+        let model = Model {
+            format_version: MODEL_FORMAT_VERSION,
+            metadata: metadata.clone(),
+            fingerprint: self.fingerprint,
+            specific_boundary: Some(specific_boundary.attributes.clone()),
+            general_boundary: canonical_attributes(general_boundary),
+            is_approximate,
+            is_partial: true,
+        };
+
+        let mut partial_path = self.path.clone().into_os_string();
+        partial_path.push(".partial");
+        model.save(&partial_path)?;
+        std::fs::rename(partial_path, &self.path)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::{ComputedBoundaries, ConcurrentSolver, HypothesisSpace, Solver};
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("ccelm-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn test_resuming_solver_from_checkpoint() {
+        let examples = enjoysport_examples();
+        let full = Solver::new(examples.clone(), &DATASET_METADATA).solve();
+
+        let path = temp_path("solver-checkpoint.json");
+        let mut solver = Solver::new(examples.clone(), &DATASET_METADATA);
+        solver.checkpointer = Some(Checkpointer::new(&path, 3));
+        solver.solve();
+
+        let checkpoint = Model::load(&path).unwrap();
+        assert_eq!(checkpoint.fingerprint.n_examples, 3);
+        assert!(checkpoint.is_partial);
+
+        let fingerprint = checkpoint.fingerprint;
+        let boundaries = checkpoint.into_boundaries(&DATASET_METADATA).unwrap();
+        let mut solver = Solver::new(
+            examples[fingerprint.n_examples..].to_vec(),
+            &DATASET_METADATA,
+        );
+        solver.specific_boundary = boundaries.specific_boundary.unwrap();
+        solver.general_boundary =
+            HypothesisSpace::maximal(boundaries.general_boundary, &DATASET_METADATA);
+        let resumed = solver.solve();
+
+        assert_eq!(resumed.specific_boundary, full.specific_boundary);
+        assert_eq!(resumed.general_boundary, full.general_boundary);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_saved_when_interrupted() {
+        let path = temp_path("interrupted-checkpoint.json");
+        let mut solver = Solver::new(enjoysport_examples(), &DATASET_METADATA);
+        solver.checkpointer = Some(Checkpointer::new(&path, 10));
+        solver.interrupt = Some(Arc::new(AtomicBool::new(true)));
+        let boundaries = solver.solve();
+        assert_eq!(boundaries.interrupted_after, Some(1));
+
+        let checkpoint = Model::load(&path).unwrap();
+        assert_eq!(checkpoint.fingerprint.n_examples, 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_resuming_concurrent_solver_from_checkpoint() {
+        let examples = enjoysport_examples();
+        let full = ConcurrentSolver::new(examples.clone(), &DATASET_METADATA, 2).solve();
+
+        let path = temp_path("concurrent-checkpoint.json");
+        let mut solver = ConcurrentSolver::new(examples.clone(), &DATASET_METADATA, 2);
+        solver.checkpointer = Some(Checkpointer::new(&path, 2));
+        solver.solve();
+
+        // The last checkpoint is taken after every training example has been processed
+        let checkpoint = Model::load(&path).unwrap();
+        assert_eq!(checkpoint.fingerprint.n_examples, 4);
+
+        let ComputedBoundaries {
+            specific_boundary,
+            general_boundary,
+            ..
+        } = checkpoint.into_boundaries(&DATASET_METADATA).unwrap();
+        let mut solver = ConcurrentSolver::new(vec![], &DATASET_METADATA, 2);
+        solver.specific_boundary = specific_boundary.unwrap();
+        solver.general_boundary = HypothesisSpace::maximal(general_boundary, &DATASET_METADATA);
+        let resumed = solver.solve();
+
+        assert_eq!(resumed.specific_boundary, full.specific_boundary);
+        assert_eq!(resumed.general_boundary, full.general_boundary);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,14 +1,56 @@
 use std::error::Error;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 use crate::reader::DatasetMetadata;
+use crate::{BeamHeuristic, Budget, DEFAULT_CHUNK_SIZE};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    #[arg(short, long, help = "Path to the input dataset of training examples")]
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Learn the specific and general boundaries of a dataset
+    Learn(LearnArgs),
+
+    /// Draw hypotheses uniformly at random from the version space of a dataset
+    Sample(SampleArgs),
+
+    /// Suggest the most informative unlabelled instance to label next
+    Suggest(SuggestArgs),
+
+    /// Learn a concept interactively by labelling the instances proposed at the terminal
+    Interactive(InteractiveArgs),
+
+    /// Learn a concept by querying an external program for the label of each proposed instance
+    QueryLearn(QueryLearnArgs),
+
+    /// Intersect version spaces learned separately, e.g. from shards of a dataset
+    Merge(MergeArgs),
+
+    /// Check that the sequential, concurrent and on-disk solvers learn the same boundaries, on a
+    /// dataset and on randomly generated ones
+    Verify(VerifyArgs),
+
+    /// Learn a single shard for `learn --shards`, reading it from stdin and printing the model to
+    /// stdout
+    #[command(hide = true)]
+    Worker(WorkerArgs),
+}
+
+#[derive(Args)]
+pub struct DatasetArgs {
+    #[arg(
+        short,
+        long,
+        help = "Path to the input dataset of training examples, or - to read it from stdin"
+    )]
     pub dataset: PathBuf,
 
     #[arg(
@@ -18,19 +60,454 @@ pub struct Cli {
         value_parser = parse_dataset_metadata
     )]
     pub metadata: DatasetMetadata,
+}
 
-    #[arg(short, long, help = "Path to output all valid hypotheses to")]
-    pub output_path: Option<PathBuf>,
+/// How to learn the boundaries of a dataset
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Algorithm {
+    /// Mitchell's candidate elimination, refining both boundaries one training example at a time
+    CandidateElimination,
+
+    /// Enumerate every hypothesis and eliminate the inconsistent ones. Only feasible for datasets
+    /// with a handful of attributes
+    ListThenEliminate,
+
+    /// Only learn the specific boundary, by generalizing it to each positive training example
+    FindS,
+
+    /// Only learn the specific boundary and a single general hypothesis, by specializing it to
+    /// each negative training example
+    FindG,
+}
+
+impl Algorithm {
+    /// Whether the algorithm learns the whole version space, rather than some of its hypotheses
+    pub fn learns_version_space(self) -> bool {
+        matches!(
+            self,
+            Algorithm::CandidateElimination | Algorithm::ListThenEliminate
+        )
+    }
+}
+
+#[derive(Args)]
+pub struct SolverArgs {
+    #[arg(
+        long,
+        help = "Algorithm to learn the boundaries with",
+        value_enum,
+        default_value_t = Algorithm::CandidateElimination
+    )]
+    pub algorithm: Algorithm,
 
     #[arg(long, help = "Whether to use the concurrent solver", required = false)]
     pub concurrent: bool,
 
     #[arg(
         long,
-        help = "How many threads the concurrent solver should use. Has no impact when not accompanied by --concurrent",
-        default_value_t = std::thread::available_parallelism().unwrap().into())
-    ]
-    pub threads: usize,
+        help = "How many threads the concurrent solver should use, every available CPU by default. Has no impact when not accompanied by --concurrent"
+    )]
+    pub threads: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Keep the general boundary in run files inside this directory rather than in memory, for version spaces that don't fit in RAM",
+        value_name = "DIRECTORY",
+        conflicts_with = "concurrent"
+    )]
+    pub disk_dir: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "How many hypotheses to store in each run file, 1048576 by default. Has no impact when not accompanied by --disk-dir"
+    )]
+    pub disk_chunk_size: Option<usize>,
+}
+
+impl SolverArgs {
+    /// How many threads the concurrent solver should use
+    pub fn threads(&self) -> usize {
+        self.threads
+            .unwrap_or_else(|| std::thread::available_parallelism().unwrap().into())
+    }
+
+    /// How many hypotheses to store in each run file of the on-disk solver
+    pub fn disk_chunk_size(&self) -> usize {
+        self.disk_chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// The options given that only the candidate elimination solvers support
+    pub fn candidate_elimination_options(&self) -> Vec<&'static str> {
+        [
+            ("--concurrent", self.concurrent),
+            ("--threads", self.threads.is_some()),
+            ("--disk-dir", self.disk_dir.is_some()),
+            ("--disk-chunk-size", self.disk_chunk_size.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, is_given)| *is_given)
+        .map(|(option, _)| option)
+        .collect()
+    }
+}
+
+#[derive(Args)]
+pub struct LearnArgs {
+    #[command(flatten)]
+    pub dataset: DatasetArgs,
+
+    #[command(flatten)]
+    pub solver: SolverArgs,
+
+    #[arg(short, long, help = "Path to output all valid hypotheses to")]
+    pub output_path: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Path to save the learned version space to as a model file that can be loaded again later"
+    )]
+    pub model_path: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Path to a model file to continue learning from. Only the examples in --dataset are processed, on top of those the model was learned from. Partial models, saved when learning stopped early, must be continued from with --resume-from instead"
+    )]
+    pub resume: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Split the training examples into this many shards, each learned by a separate worker process, and merge the results"
+    )]
+    pub shards: Option<usize>,
+
+    #[arg(
+        long,
+        help = "How many times to run a shard's worker before giving up on it. Has no impact when not accompanied by --shards",
+        default_value_t = 3
+    )]
+    pub shard_attempts: usize,
+
+    #[arg(
+        long,
+        help = "Check the learned boundaries afterwards: every hypothesis in them must be consistent with each training example in --dataset, the specific boundary must be more specific than every general hypothesis and no general hypothesis may subsume another"
+    )]
+    pub check: bool,
+
+    #[command(flatten)]
+    pub checkpoint: CheckpointArgs,
+
+    #[command(flatten)]
+    pub budget: BudgetArgs,
+}
+
+#[derive(Args)]
+pub struct CheckpointArgs {
+    #[arg(
+        long,
+        help = "Save a checkpoint of the version space after every N training examples, and when interrupted",
+        value_name = "N",
+        conflicts_with_all = ["resume", "shards"]
+    )]
+    pub checkpoint_every: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Path to save checkpoints to. Has no impact when not accompanied by --checkpoint-every",
+        default_value = "checkpoint.json"
+    )]
+    pub checkpoint_path: PathBuf,
+
+    #[arg(
+        long,
+        help = "Path to a checkpoint to continue an interrupted solve from. --dataset must be the same dataset the checkpoint was saved while learning, its already processed training examples are skipped",
+        conflicts_with_all = ["resume", "shards"]
+    )]
+    pub resume_from: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct BudgetArgs {
+    #[arg(
+        long,
+        help = "Abort once the general boundary holds more than N hypotheses, or narrow it to N hypotheses when accompanied by --beam",
+        value_name = "N",
+        conflicts_with = "shards"
+    )]
+    pub max_general: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Abort once learning has taken longer than this many seconds",
+        value_name = "SECONDS",
+        conflicts_with = "shards"
+    )]
+    pub time_limit: Option<f64>,
+
+    #[arg(
+        long,
+        help = "Abort once the general boundary is estimated to take up more than this many megabytes, or narrow it to fit when accompanied by --beam",
+        value_name = "MEGABYTES",
+        conflicts_with = "shards"
+    )]
+    pub memory_limit: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Instead of aborting when --max-general or --memory-limit is exceeded, keep only the most promising general hypotheses according to this heuristic. The general boundary is then approximate",
+        value_name = "HEURISTIC"
+    )]
+    pub beam: Option<BeamHeuristic>,
+}
+
+impl BudgetArgs {
+    pub fn budget(&self) -> Budget {
+        Budget {
+            max_general: self.max_general,
+            time_limit: self.time_limit.map(Duration::from_secs_f64),
+            memory_limit: self.memory_limit.map(|megabytes| megabytes * 1024 * 1024),
+            beam: self.beam,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct SampleArgs {
+    #[command(flatten)]
+    pub dataset: DatasetArgs,
+
+    #[command(flatten)]
+    pub solver: SolverArgs,
+
+    #[arg(short, help = "How many hypotheses to draw", default_value_t = 1)]
+    pub n: usize,
+
+    #[arg(
+        long,
+        help = "Seed for the random number generator",
+        default_value_t = 0
+    )]
+    pub seed: u64,
+}
+
+#[derive(Args)]
+pub struct SuggestArgs {
+    #[command(flatten)]
+    pub dataset: DatasetArgs,
+
+    #[command(flatten)]
+    pub solver: SolverArgs,
+
+    #[arg(
+        short,
+        long,
+        help = "Path to a pool of unlabelled instances to pick from. The instance space described by the metadata is searched if omitted"
+    )]
+    pub pool: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "How many hypotheses to sample from the version space when estimating how evenly an instance splits it",
+        default_value_t = 256
+    )]
+    pub samples: usize,
+
+    #[arg(
+        long,
+        help = "Seed for the random number generator",
+        default_value_t = 0
+    )]
+    pub seed: u64,
+}
+
+#[derive(Args)]
+pub struct InteractiveArgs {
+    #[arg(
+        short,
+        long,
+        help = "Path to a dataset of already labelled training examples to start from"
+    )]
+    pub dataset: Option<PathBuf>,
+
+    #[arg(
+        short,
+        long,
+        help = "Path to a YAML-formatted dataset metadata file. Contains metadata describing the dataset itself.",
+        value_parser = parse_dataset_metadata
+    )]
+    pub metadata: DatasetMetadata,
+
+    #[command(flatten)]
+    pub solver: SolverArgs,
+
+    #[arg(
+        short,
+        long,
+        help = "Path to save the labelled training examples to, including any read from --dataset"
+    )]
+    pub output_path: PathBuf,
+
+    #[arg(
+        long,
+        help = "How many hypotheses to sample from the version space when estimating how evenly an instance splits it",
+        default_value_t = 256
+    )]
+    pub samples: usize,
+
+    #[arg(
+        long,
+        help = "Seed for the random number generator",
+        default_value_t = 0
+    )]
+    pub seed: u64,
+}
+
+#[derive(Args)]
+pub struct QueryLearnArgs {
+    #[arg(
+        short,
+        long,
+        help = "Path to a dataset of already labelled training examples to start from"
+    )]
+    pub dataset: Option<PathBuf>,
+
+    #[arg(
+        short,
+        long,
+        help = "Path to a YAML-formatted dataset metadata file. Contains metadata describing the dataset itself.",
+        value_parser = parse_dataset_metadata
+    )]
+    pub metadata: DatasetMetadata,
+
+    #[command(flatten)]
+    pub solver: SolverArgs,
+
+    #[arg(
+        long,
+        help = "Program to label instances with. Receives each instance as a comma-separated CSV line on stdin, whatever the dataset's delimiter, and must print true or false"
+    )]
+    pub oracle: PathBuf,
+
+    #[arg(last = true, help = "Arguments to pass to the oracle program")]
+    pub oracle_args: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Stop after this many queries even if the version space hasn't converged"
+    )]
+    pub max_queries: Option<usize>,
+
+    #[arg(short, long, help = "Path to output all valid hypotheses to")]
+    pub output_path: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "How many hypotheses to sample from the version space when estimating how evenly an instance splits it",
+        default_value_t = 256
+    )]
+    pub samples: usize,
+
+    #[arg(
+        long,
+        help = "Seed for the random number generator",
+        default_value_t = 0
+    )]
+    pub seed: u64,
+}
+
+#[derive(Args)]
+pub struct MergeArgs {
+    #[arg(
+        required = true,
+        num_args = 2..,
+        help = "Paths to the model files to merge. All models must share the same dataset metadata"
+    )]
+    pub models: Vec<PathBuf>,
+
+    #[arg(short, long, help = "Path to output all valid hypotheses to")]
+    pub output_path: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Path to save the merged version space to as a model file"
+    )]
+    pub model_path: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    #[arg(
+        short,
+        long,
+        help = "Path to a dataset of training examples to learn with every solver",
+        requires = "metadata"
+    )]
+    pub dataset: Option<PathBuf>,
+
+    #[arg(
+        short,
+        long,
+        help = "Path to a YAML-formatted dataset metadata file. Contains metadata describing the dataset itself.",
+        value_parser = parse_dataset_metadata,
+        requires = "dataset"
+    )]
+    pub metadata: Option<DatasetMetadata>,
+
+    #[arg(
+        long,
+        help = "Only learn the first N training examples of --dataset",
+        value_name = "N"
+    )]
+    pub examples: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Thread counts to run the concurrent solver with, separated by commas",
+        value_delimiter = ',',
+        default_value = "1,2,4"
+    )]
+    pub threads: Vec<usize>,
+
+    #[arg(
+        long,
+        help = "How many randomly generated datasets to learn with every solver",
+        value_name = "N",
+        default_value_t = 0
+    )]
+    pub random: usize,
+
+    #[arg(
+        long,
+        help = "How many attributes each randomly generated dataset has",
+        default_value_t = 8
+    )]
+    pub random_attributes: usize,
+
+    #[arg(
+        long,
+        help = "How many values each attribute of a randomly generated dataset takes",
+        default_value_t = 3
+    )]
+    pub random_values: usize,
+
+    #[arg(
+        long,
+        help = "How many training examples each randomly generated dataset has",
+        default_value_t = 20
+    )]
+    pub random_examples: usize,
+
+    #[arg(
+        long,
+        help = "Seed for the first randomly generated dataset, each of the others uses the next seed",
+        default_value_t = 0
+    )]
+    pub seed: u64,
+}
+
+#[derive(Args)]
+pub struct WorkerArgs {
+    #[command(flatten)]
+    pub solver: SolverArgs,
 }
 
 fn parse_dataset_metadata(path: &str) -> Result<DatasetMetadata, Box<dyn Error + Sync + Send>> {
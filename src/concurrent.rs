@@ -1,23 +1,38 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
+
+use itertools::Itertools;
 use log::{info, trace};
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use rayon::{ThreadPool, ThreadPoolBuilder};
 
+use crate::learner::{learn, Learner};
 use crate::reader::DatasetMetadata;
-use crate::{ComputedBoundaries, Hypothesis, TrainingExample};
+use crate::version_space::Specializer;
+use crate::{
+    Budget, BudgetExceeded, Checkpointer, ComputedBoundaries, Hypothesis, HypothesisArena,
+    HypothesisSpace, TrainingExample,
+};
 
 #[derive(Debug)]
-pub struct ConcurrentSolver<'a> {
-    pub specific_boundary: Hypothesis<'a>,
-    pub general_boundary: Vec<Hypothesis<'a>>,
+pub struct ConcurrentSolver {
+    pub specific_boundary: Hypothesis,
+    pub general_boundary: HypothesisSpace,
+    pub checkpointer: Option<Checkpointer>,
+    pub interrupt: Option<Arc<AtomicBool>>,
+    pub budget: Budget,
     training_examples: Vec<TrainingExample>,
-    dataset_metadata: &'a DatasetMetadata,
+    dataset_metadata: Arc<DatasetMetadata>,
     threadpool: ThreadPool,
 }
 
-impl<'a> ConcurrentSolver<'a> {
+impl ConcurrentSolver {
     pub fn new(
         training_examples: Vec<TrainingExample>,
-        dataset_metadata: &'a DatasetMetadata,
+        dataset_metadata: &Arc<DatasetMetadata>,
         n_threads: usize,
     ) -> Self {
         let attribute_count = dataset_metadata.columns.len();
@@ -28,77 +43,201 @@ impl<'a> ConcurrentSolver<'a> {
 
         Self {
             specific_boundary: Hypothesis::specific(attribute_count, dataset_metadata),
-            general_boundary: vec![Hypothesis::general(attribute_count, dataset_metadata)],
+            general_boundary: HypothesisSpace::maximal(
+                [Hypothesis::general(attribute_count, dataset_metadata)],
+                dataset_metadata,
+            ),
+            checkpointer: None,
+            interrupt: None,
+            budget: Budget::default(),
             training_examples,
-            dataset_metadata,
+            dataset_metadata: Arc::clone(dataset_metadata),
             threadpool,
         }
     }
 
-    pub fn solve(mut self) -> ComputedBoundaries<'a> {
-        self.threadpool.scope(|_| {
-            let n_training_examples = self.training_examples.len();
+    pub fn solve(self) -> ComputedBoundaries {
+        let Ok(boundaries) = self.solve_stream(std::iter::empty::<Result<_, Infallible>>());
+        boundaries
+    }
+
+    /// Learn from a stream of training examples without buffering them, e.g. rows as they are
+    /// read from a [`crate::DatasetReader`]. Any training examples passed to
+    /// [`ConcurrentSolver::new`] are processed first. Returns the first error in the stream.
+    ///
+    /// If a [`Checkpointer`] is set, checkpoints are saved while solving. Failing to save a
+    /// checkpoint is logged rather than aborting the solve.
+    ///
+    /// If the interrupt flag is raised, solving stops once the current training example has been
+    /// processed and the boundaries learned so far are returned, flagged as interrupted. The same
+    /// happens when the [`Budget`] is exceeded without a beam to fall back on.
+    pub fn solve_stream<I, E>(mut self, training_examples: I) -> Result<ComputedBoundaries, E>
+    where
+        I: IntoIterator<Item = Result<TrainingExample, E>>,
+    {
+        let training_examples = std::mem::take(&mut self.training_examples)
+            .into_iter()
+            .map(Ok)
+            .chain(training_examples);
+
+        // The solver itself is the learner, so the options the loop needs are taken out of it
+        let mut checkpointer = self.checkpointer.take();
+        let interrupt = self.interrupt.take();
+        let budget = std::mem::take(&mut self.budget);
+
+        learn(
+            self,
+            training_examples,
+            checkpointer.as_mut(),
+            &interrupt,
+            &budget,
+        )
+    }
+}
+
+impl<E> Learner<E> for ConcurrentSolver {
+    fn general_boundary_len(&self) -> usize {
+        self.general_boundary.len()
+    }
+
+    fn add_example(&mut self, example: TrainingExample) -> Result<(), E> {
+        if example.is_positive {
+            info!("Processing positive training example: {example}");
+            // Remove any hypothesis that is inconsistent with d
+            self.general_boundary.retain_covering(&example);
+
+            trace!("Inconsistent hypotheses removed from general boundary");
+
+            self.specific_boundary = self.specific_boundary.generalize(&example);
+
+            trace!("Specific hypothesis barrier refined");
+        } else {
+            info!("Processing negative training example: {example}");
+
+            // Only the hypotheses that cover the example need to be specialized, which is done
+            // in parallel with each thread interning into an arena of its own. The arenas are
+            // then merged, which collapses duplicates across threads, and the specializations
+            // are checked for minimality in parallel too. The parallel iterators are run inside
+            // the pool so that they use its threads.
             let column_data = &self.dataset_metadata.columns;
-            for (index, example) in self.training_examples.into_iter().enumerate() {
-                info!(
-                    "{index}/{n_training_examples} | {} general hypotheses",
-                    self.general_boundary.len()
-                );
-
-                if example.is_positive {
-                    info!("Processing positive training example: {example}");
-                    // Remove any hypothesis that is inconsistent with d
-                    self.general_boundary = self
-                        .general_boundary
-                        .into_par_iter()
-                        .filter(|h| h.is_consistent(&example))
-                        .collect();
-                    // .retain(|hypothesis| hypothesis.is_consistent(&example));
-
-                    trace!("Inconsistent hypotheses removed from general boundary");
-
-                    self.specific_boundary = self.specific_boundary.generalize(&example);
-
-                    trace!("Specific hypothesis barrier refined");
-                } else {
-                    info!("Processing negative training example: {example}");
-                    // Remove any hypothesis that is inconsistent with d
-                    // specific_hypothesis.retain(|hypothesis| hypothesis.is_consistent(&example));
-
-                    self.general_boundary = self
-                        .general_boundary
-                        .into_par_iter()
-                        .flat_map_iter(|hypothesis| {
-                            if hypothesis.is_consistent(&example) {
-                                vec![hypothesis]
-                            } else {
-                                let mut specializations =
-                                    hypothesis.specialize(&example, column_data.as_slice());
-
-                                specializations.retain(|specialization| {
-                                    specialization.is_more_general(&self.specific_boundary)
-                                });
-
-                                specializations
-                            }
-                        })
-                        .collect();
-                }
-
-                info!("Successfully processed example");
-            }
-
-            let cloned_boundary = self.general_boundary.clone();
-            self.general_boundary.retain(|hypothesis| {
-                cloned_boundary
-                    .iter()
-                    .all(|other| hypothesis == other || !hypothesis.is_more_general(other))
+            let mut covering = HypothesisArena::new(column_data.len());
+            self.general_boundary
+                .take_covering_into(&example, &mut covering);
+
+            let specializer = Specializer::new(&example, &self.specific_boundary, column_data);
+            let general_boundary = &mut self.general_boundary;
+            self.threadpool.install(|| {
+                let specializations = covering
+                    .ids()
+                    .collect_vec()
+                    .into_par_iter()
+                    .fold(
+                        || HypothesisArena::new(column_data.len()),
+                        |mut specializations, id| {
+                            specializer.specialize_into(covering.get(id), &mut specializations);
+                            specializations
+                        },
+                    )
+                    .reduce(
+                        || HypothesisArena::new(column_data.len()),
+                        |mut specializations, other| {
+                            specializations.extend_from(&other);
+                            specializations
+                        },
+                    );
+
+                general_boundary.par_extend_maximal(&specializations);
             });
+        }
+
+        info!("Successfully processed example");
+        Ok(())
+    }
+
+    fn enforce_budget(
+        &mut self,
+        budget: &Budget,
+        started: Instant,
+        n_examples: usize,
+    ) -> Result<bool, BudgetExceeded> {
+        budget.enforce(started, n_examples, &mut self.general_boundary)
+    }
+
+    fn save_checkpoint(
+        &self,
+        checkpointer: &Checkpointer,
+        is_approximate: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        checkpointer.save(
+            &self.specific_boundary,
+            &self.general_boundary.to_vec(),
+            is_approximate,
+            &self.dataset_metadata,
+        )
+    }
 
-            ComputedBoundaries {
-                specific_boundary: Some(self.specific_boundary),
-                general_boundary: self.general_boundary,
-            }
+    fn into_boundaries(self) -> Result<ComputedBoundaries, E> {
+        Ok(ComputedBoundaries {
+            specific_boundary: Some(self.specific_boundary),
+            general_boundary: self.general_boundary.to_vec(),
+            ..Default::default()
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DatasetReader, Solver};
+
+    fn load(dataset: &str, n_examples: usize) -> (Vec<TrainingExample>, Arc<DatasetMetadata>) {
+        let directory = format!("./data/{dataset}");
+        let metadata: DatasetMetadata = serde_yaml::from_reader(
+            std::fs::File::open(format!("{directory}/metadata.yaml")).unwrap(),
+        )
+        .unwrap();
+
+        let path = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| path.file_stem().is_some_and(|stem| stem == "dataset"))
+            .unwrap();
+        let training_examples = DatasetReader::new(path, metadata.clone())
+            .unwrap()
+            .take(n_examples)
+            .collect::<Result<Vec<TrainingExample>, _>>()
+            .unwrap();
+
+        (training_examples, Arc::new(metadata))
+    }
+
+    #[test]
+    fn test_matches_sequential_solver() {
+        for (dataset, n_examples) in [("enjoysport", 4), ("boundaries-germanic", 10)] {
+            let (training_examples, metadata) = load(dataset, n_examples);
+
+            let sequential = Solver::new(training_examples.clone(), &metadata).solve();
+            let concurrent = ConcurrentSolver::new(training_examples, &metadata, 4).solve();
+
+            assert!(!sequential.general_boundary.is_empty());
+            assert_eq!(concurrent.specific_boundary, sequential.specific_boundary);
+            assert_eq!(concurrent.general_boundary, sequential.general_boundary);
+        }
+    }
+
+    #[test]
+    fn test_boundaries_are_canonical_for_any_number_of_threads() {
+        let (training_examples, metadata) = load("boundaries-germanic", 10);
+
+        let single = ConcurrentSolver::new(training_examples.clone(), &metadata, 1).solve();
+        assert!(single.is_canonical());
+
+        for n_threads in [2, 3] {
+            let multi =
+                ConcurrentSolver::new(training_examples.clone(), &metadata, n_threads).solve();
+            assert!(multi.is_canonical());
+            assert_eq!(multi.specific_boundary, single.specific_boundary);
+            assert_eq!(multi.general_boundary, single.general_boundary);
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use crate::reader::DatasetMetadata;
+use crate::{Attribute, Hypothesis};
+
+/// How many hypotheses are stored in each run file by default
+pub const DEFAULT_CHUNK_SIZE: usize = 1 << 20;
+
+/// A general boundary kept on disk rather than in memory, for version spaces whose general
+/// boundary doesn't fit in RAM.
+///
+/// Hypotheses are stored in run files of at most `chunk_size` hypotheses each, one byte per
+/// attribute, inside a temporary directory that is removed when the boundary is dropped. The
+/// boundary is only ever accessed by streaming through it, so at most one hypothesis or one batch
+/// of them is held in memory at a time, and each run file is deleted as soon as it has been read.
+pub struct DiskBoundary {
+    directory: TempDir,
+    chunk_size: usize,
+    runs: Vec<PathBuf>,
+    len: usize,
+    n_runs_written: usize,
+    dataset_metadata: Arc<DatasetMetadata>,
+}
+
+const NO_VALUE_BYTE: u8 = 0;
+const ANY_BYTE: u8 = 1;
+const VALUE_OFFSET: u8 = 2;
+
+impl DiskBoundary {
+    /// Store `hypotheses` in a new temporary directory inside `parent_directory`
+    pub fn new<P, I>(
+        parent_directory: P,
+        chunk_size: usize,
+        hypotheses: I,
+        dataset_metadata: &Arc<DatasetMetadata>,
+    ) -> Result<Self, Box<dyn Error>>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = Hypothesis>,
+    {
+        let max_values = usize::from(u8::MAX - VALUE_OFFSET) + 1;
+        if let Some(index) = dataset_metadata
+            .columns
+            .iter()
+            .position(|values| values.len() > max_values)
+        {
+            return Err(format!(
+                "Column {index} has more than {max_values} values, which can't be stored on disk"
+            )
+            .into());
+        }
+
+        let mut boundary = Self {
+            directory: tempfile::Builder::new()
+                .prefix("ccelm-general-boundary-")
+                .tempdir_in(parent_directory)?,
+            chunk_size: chunk_size.max(1),
+            runs: vec![],
+            len: 0,
+            n_runs_written: 0,
+            dataset_metadata: Arc::clone(dataset_metadata),
+        };
+
+        let (runs, len) = boundary.write_runs(hypotheses.into_iter().map(Ok))?;
+        boundary.runs = runs;
+        boundary.len = len;
+
+        Ok(boundary)
+    }
+
+    /// How many hypotheses are stored
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Replace every hypothesis with the hypotheses returned for it, streaming the boundary from
+    /// its current run files into new ones
+    pub fn flat_map<F>(&mut self, mut f: F) -> Result<(), Box<dyn Error>>
+    where
+        F: FnMut(Hypothesis) -> Vec<Hypothesis>,
+    {
+        let runs = std::mem::take(&mut self.runs);
+        self.len = 0;
+
+        let hypotheses =
+            RunReader::new(runs, &self.dataset_metadata).flat_map(|hypothesis| match hypothesis {
+                Ok(hypothesis) => f(hypothesis).into_iter().map(Ok).collect(),
+                Err(err) => vec![Err(err)],
+            });
+
+        let (runs, len) = self.write_runs(hypotheses)?;
+        self.runs = runs;
+        self.len = len;
+
+        Ok(())
+    }
+
+    /// Like [`DiskBoundary::flat_map`], but passing the hypotheses to `f` in batches of up to
+    /// `chunk_size` at a time, so that each batch can be processed as a whole. This holds a batch
+    /// in memory rather than a single hypothesis.
+    pub fn flat_map_batches<F>(&mut self, mut f: F) -> Result<(), Box<dyn Error>>
+    where
+        F: FnMut(Vec<Hypothesis>) -> Vec<Hypothesis>,
+    {
+        let runs = std::mem::take(&mut self.runs);
+        self.len = 0;
+
+        let mut reader = RunReader::new(runs, &self.dataset_metadata);
+        let chunk_size = self.chunk_size;
+        let batches = std::iter::from_fn(|| {
+            match reader
+                .by_ref()
+                .take(chunk_size)
+                .collect::<Result<Vec<Hypothesis>, _>>()
+            {
+                Ok(batch) if batch.is_empty() => None,
+                batch => Some(batch),
+            }
+        });
+        let hypotheses = batches.flat_map(|batch| match batch {
+            Ok(batch) => f(batch).into_iter().map(Ok).collect(),
+            Err(err) => vec![Err(err)],
+        });
+
+        let (runs, len) = self.write_runs(hypotheses)?;
+        self.runs = runs;
+        self.len = len;
+
+        Ok(())
+    }
+
+    /// Keep only the hypotheses for which `f` returns true
+    pub fn retain<F>(&mut self, mut f: F) -> Result<(), Box<dyn Error>>
+    where
+        F: FnMut(&Hypothesis) -> bool,
+    {
+        self.flat_map(|hypothesis| {
+            if f(&hypothesis) {
+                vec![hypothesis]
+            } else {
+                vec![]
+            }
+        })
+    }
+
+    /// Read every hypothesis back into memory, emptying the boundary
+    pub fn take_all(&mut self) -> Result<Vec<Hypothesis>, Box<dyn Error>> {
+        self.len = 0;
+        RunReader::new(std::mem::take(&mut self.runs), &self.dataset_metadata).collect()
+    }
+
+    /// Read every hypothesis without removing it
+    pub fn to_vec(&self) -> Result<Vec<Hypothesis>, Box<dyn Error>> {
+        let mut reader = RunReader::new(self.runs.clone(), &self.dataset_metadata);
+        reader.delete_after_reading = false;
+        reader.collect()
+    }
+
+    fn write_runs<I>(&mut self, hypotheses: I) -> Result<(Vec<PathBuf>, usize), Box<dyn Error>>
+    where
+        I: IntoIterator<Item = Result<Hypothesis, Box<dyn Error>>>,
+    {
+        let mut runs = vec![];
+        let mut len = 0;
+        let mut writer: Option<BufWriter<File>> = None;
+
+        for hypothesis in hypotheses {
+            let hypothesis = hypothesis?;
+
+            if len % self.chunk_size == 0 {
+                if let Some(mut writer) = writer.take() {
+                    writer.flush()?;
+                }
+
+                let path = self
+                    .directory
+                    .path()
+                    .join(format!("run-{}", self.n_runs_written));
+                self.n_runs_written += 1;
+                writer = Some(BufWriter::new(File::create(&path)?));
+                runs.push(path);
+            }
+
+            let bytes: Vec<u8> = hypothesis
+                .attributes
+                .iter()
+                .map(|attribute| match attribute {
+                    Attribute::NoValue => NO_VALUE_BYTE,
+                    Attribute::Any => ANY_BYTE,
+                    Attribute::Value(value) => value + VALUE_OFFSET,
+                })
+                .collect();
+            writer.as_mut().unwrap().write_all(&bytes)?;
+            len += 1;
+        }
+
+        if let Some(mut writer) = writer {
+            writer.flush()?;
+        }
+
+        Ok((runs, len))
+    }
+}
+
+/// Streams hypotheses out of a sequence of run files
+struct RunReader {
+    runs: std::vec::IntoIter<PathBuf>,
+    current: Option<(PathBuf, BufReader<File>)>,
+    delete_after_reading: bool,
+    dataset_metadata: Arc<DatasetMetadata>,
+}
+
+impl RunReader {
+    fn new(runs: Vec<PathBuf>, dataset_metadata: &Arc<DatasetMetadata>) -> Self {
+        Self {
+            runs: runs.into_iter(),
+            current: None,
+            delete_after_reading: true,
+            dataset_metadata: Arc::clone(dataset_metadata),
+        }
+    }
+}
+
+impl Iterator for RunReader {
+    type Item = Result<Hypothesis, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut bytes = vec![0; self.dataset_metadata.columns.len()];
+
+        loop {
+            let Some((path, reader)) = &mut self.current else {
+                let path = self.runs.next()?;
+                match File::open(&path) {
+                    Ok(file) => self.current = Some((path, BufReader::new(file))),
+                    Err(err) => return Some(Err(err.into())),
+                }
+                continue;
+            };
+
+            match reader.read_exact(&mut bytes) {
+                Ok(()) => break,
+                Err(err) if err.kind() == ErrorKind::UnexpectedEof => {
+                    if self.delete_after_reading {
+                        if let Err(err) = std::fs::remove_file(path) {
+                            return Some(Err(err.into()));
+                        }
+                    }
+                    self.current = None;
+                }
+                Err(err) => return Some(Err(err.into())),
+            }
+        }
+
+        let attributes = bytes
+            .into_iter()
+            .map(|byte| match byte {
+                NO_VALUE_BYTE => Attribute::NoValue,
+                ANY_BYTE => Attribute::Any,
+                value => Attribute::Value(value - VALUE_OFFSET),
+            })
+            .collect();
+
+        Some(Ok(Hypothesis {
+            attributes,
+            dataset_metadata: Arc::clone(&self.dataset_metadata),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::DATASET_METADATA;
+
+    #[test]
+    fn test_round_trip_across_runs() {
+        let hypotheses = vec![
+            Hypothesis::from_str("Sunny,?,?,?,?,?", &DATASET_METADATA).unwrap(),
+            Hypothesis::from_str("?,Warm,?,?,?,?", &DATASET_METADATA).unwrap(),
+            Hypothesis::from_str("Cloudy,Cold,High,Weak,Cool,Change", &DATASET_METADATA).unwrap(),
+            Hypothesis::specific(6, &DATASET_METADATA),
+            Hypothesis::general(6, &DATASET_METADATA),
+        ];
+
+        let mut boundary = DiskBoundary::new(
+            std::env::temp_dir(),
+            2,
+            hypotheses.clone(),
+            &DATASET_METADATA,
+        )
+        .unwrap();
+        assert_eq!(boundary.len(), 5);
+        assert_eq!(boundary.runs.len(), 3);
+
+        assert_eq!(boundary.to_vec().unwrap(), hypotheses);
+
+        boundary
+            .retain(|hypothesis| hypothesis.attributes[0] == Attribute::Any)
+            .unwrap();
+        assert_eq!(boundary.len(), 2);
+        assert_eq!(
+            boundary.take_all().unwrap(),
+            vec![hypotheses[1].clone(), hypotheses[4].clone()]
+        );
+        assert!(boundary.is_empty());
+    }
+
+    #[test]
+    fn test_flat_map_batches() {
+        let hypotheses = (0..5)
+            .map(|_| Hypothesis::general(6, &DATASET_METADATA))
+            .collect::<Vec<Hypothesis>>();
+        let mut boundary =
+            DiskBoundary::new(std::env::temp_dir(), 2, hypotheses, &DATASET_METADATA).unwrap();
+
+        let mut batch_sizes = vec![];
+        boundary
+            .flat_map_batches(|batch| {
+                batch_sizes.push(batch.len());
+                batch.into_iter().take(1).collect()
+            })
+            .unwrap();
+
+        assert_eq!(batch_sizes, [2, 2, 1]);
+        assert_eq!(boundary.len(), 3);
+    }
+}
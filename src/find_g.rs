@@ -0,0 +1,143 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use crate::reader::DatasetMetadata;
+use crate::{ComputedBoundaries, FindS, Hypothesis, TrainingExample};
+
+/// Learns a single general hypothesis consistent with the training examples, the counterpart of
+/// [`FindS`]. Starting from the most general hypothesis, it is specialized just enough to exclude
+/// each negative training example in turn.
+///
+/// Unlike generalizing, specializing has many minimal outcomes, so they are narrowed down with
+/// the specific boundary learned from the positive examples by [`FindS`]: of the minimal
+/// specializations that are still more general than it, the first in the canonical order is
+/// kept. The negative examples are therefore buffered until every positive one has been seen.
+///
+/// The result is the specific boundary along with a general boundary of that one hypothesis, like
+/// the candidate elimination solvers narrowed to a beam of width one. It is flagged approximate
+/// since the other maximally general hypotheses are not learned. If the specific boundary covers a
+/// negative example, no specialization excludes it and the general boundary is left empty.
+#[derive(Clone, Debug)]
+pub struct FindG {
+    pub general_hypothesis: Hypothesis,
+    training_examples: Vec<TrainingExample>,
+    dataset_metadata: Arc<DatasetMetadata>,
+}
+
+impl FindG {
+    pub fn new(
+        training_examples: Vec<TrainingExample>,
+        dataset_metadata: &Arc<DatasetMetadata>,
+    ) -> Self {
+        Self {
+            general_hypothesis: Hypothesis::general(
+                dataset_metadata.columns.len(),
+                dataset_metadata,
+            ),
+            training_examples,
+            dataset_metadata: Arc::clone(dataset_metadata),
+        }
+    }
+
+    pub fn solve(self) -> ComputedBoundaries {
+        let Ok(boundaries) = self.solve_stream(std::iter::empty::<Result<_, Infallible>>());
+        boundaries
+    }
+
+    /// Learn from a stream of training examples. Any training examples passed to [`FindG::new`]
+    /// are processed first. Returns the first error in the stream.
+    pub fn solve_stream<I, E>(self, training_examples: I) -> Result<ComputedBoundaries, E>
+    where
+        I: IntoIterator<Item = Result<TrainingExample, E>>,
+    {
+        let mut negative_examples = vec![];
+        let training_examples = self
+            .training_examples
+            .into_iter()
+            .map(Ok)
+            .chain(training_examples)
+            .filter(|example| match example {
+                Ok(example) if !example.is_positive => {
+                    negative_examples.push(example.clone());
+                    false
+                }
+                _ => true,
+            });
+
+        let mut boundaries =
+            FindS::new(vec![], &self.dataset_metadata).solve_stream(training_examples)?;
+        let specific_boundary = boundaries.specific_boundary.as_ref().unwrap();
+
+        let mut general_hypothesis = Some(self.general_hypothesis);
+        for example in negative_examples {
+            general_hypothesis = general_hypothesis.and_then(|hypothesis| {
+                if !hypothesis.classify(&example) {
+                    return Some(hypothesis);
+                }
+
+                hypothesis
+                    .specialize(&example, &self.dataset_metadata.columns)
+                    .into_iter()
+                    .filter(|specialization| specialization.is_more_general(specific_boundary))
+                    .min()
+            });
+        }
+
+        boundaries.general_boundary = general_hypothesis.into_iter().collect();
+        boundaries.is_approximate = true;
+
+        Ok(boundaries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::{random_dataset, Solver};
+
+    #[test]
+    fn test_enjoysport() {
+        let training_examples = enjoysport_examples();
+
+        let boundaries = FindG::new(training_examples.clone(), &DATASET_METADATA).solve();
+
+        assert!(boundaries.is_approximate);
+        assert_eq!(
+            boundaries.specific_boundary.as_ref().unwrap().to_string(),
+            "⟨Sunny, Warm, ?, Strong, ?, ?⟩"
+        );
+        assert_eq!(
+            boundaries
+                .general_boundary
+                .iter()
+                .map(|hypothesis| hypothesis.to_string())
+                .collect::<Vec<String>>(),
+            ["⟨Sunny, ?, ?, ?, ?, ?⟩"]
+        );
+        assert!(boundaries.verify(&training_examples).is_empty());
+    }
+
+    #[test]
+    fn test_learns_a_hypothesis_of_the_version_space() {
+        for seed in 0..10 {
+            let (metadata, training_examples) = random_dataset(6, 3, 20, seed).unwrap();
+            let metadata = Arc::new(metadata);
+
+            let expected = Solver::new(training_examples.clone(), &metadata).solve();
+            let actual = FindG::new(training_examples.clone(), &metadata).solve();
+
+            assert_eq!(actual.specific_boundary, expected.specific_boundary);
+            if expected.general_boundary.is_empty() {
+                continue;
+            }
+
+            assert_eq!(actual.general_boundary.len(), 1);
+            assert!(expected
+                .general_boundary
+                .iter()
+                .any(|general| general.is_more_general(&actual.general_boundary[0])));
+            assert!(actual.verify(&training_examples).is_empty());
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use crate::reader::DatasetMetadata;
+use crate::{ComputedBoundaries, Hypothesis, TrainingExample};
+
+/// Learns only the most specific hypothesis consistent with the positive training examples,
+/// using Mitchell's Find-S algorithm. Starting from the empty hypothesis, it is generalized just
+/// enough to cover each positive example in turn, while negative examples are ignored.
+///
+/// This is the specific boundary [`crate::Solver`] learns, without the cost of maintaining the
+/// general boundary alongside it. The general boundary of the result is left empty. If the
+/// training examples can't be described by a conjunctive hypothesis, the specific boundary may
+/// cover negative examples, which [`ComputedBoundaries::verify`] reports.
+#[derive(Clone, Debug)]
+pub struct FindS {
+    pub specific_boundary: Hypothesis,
+    training_examples: Vec<TrainingExample>,
+}
+
+impl FindS {
+    pub fn new(
+        training_examples: Vec<TrainingExample>,
+        dataset_metadata: &Arc<DatasetMetadata>,
+    ) -> Self {
+        Self {
+            specific_boundary: Hypothesis::specific(
+                dataset_metadata.columns.len(),
+                dataset_metadata,
+            ),
+            training_examples,
+        }
+    }
+
+    pub fn solve(self) -> ComputedBoundaries {
+        let Ok(boundaries) = self.solve_stream(std::iter::empty::<Result<_, Infallible>>());
+        boundaries
+    }
+
+    /// Learn from a stream of training examples without buffering them. Any training examples
+    /// passed to [`FindS::new`] are processed first. Returns the first error in the stream.
+    pub fn solve_stream<I, E>(mut self, training_examples: I) -> Result<ComputedBoundaries, E>
+    where
+        I: IntoIterator<Item = Result<TrainingExample, E>>,
+    {
+        let training_examples = self
+            .training_examples
+            .into_iter()
+            .map(Ok)
+            .chain(training_examples);
+
+        for example in training_examples {
+            let example = example?;
+            if example.is_positive {
+                self.specific_boundary = self.specific_boundary.generalize(&example);
+            }
+        }
+
+        Ok(ComputedBoundaries {
+            specific_boundary: Some(self.specific_boundary),
+            general_boundary: vec![],
+            interrupted_after: None,
+            budget_exceeded: None,
+            is_approximate: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::{random_dataset, Solver};
+
+    #[test]
+    fn test_enjoysport() {
+        let training_examples = enjoysport_examples();
+
+        let boundaries = FindS::new(training_examples.clone(), &DATASET_METADATA).solve();
+
+        assert_eq!(
+            boundaries.specific_boundary.unwrap().to_string(),
+            "⟨Sunny, Warm, ?, Strong, ?, ?⟩"
+        );
+        assert!(boundaries.general_boundary.is_empty());
+    }
+
+    #[test]
+    fn test_matches_solver_specific_boundary() {
+        for seed in 0..10 {
+            let (metadata, training_examples) = random_dataset(6, 3, 20, seed).unwrap();
+            let metadata = Arc::new(metadata);
+
+            let expected = Solver::new(training_examples.clone(), &metadata).solve();
+            let actual = FindS::new(training_examples, &metadata).solve();
+
+            assert_eq!(actual.specific_boundary, expected.specific_boundary);
+        }
+    }
+}
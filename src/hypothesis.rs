@@ -1,7 +1,9 @@
 use std::fmt::Display;
 use std::str::ParseBoolError;
+use std::sync::Arc;
 
 use derivative::Derivative;
+use serde::{Serialize, Serializer};
 
 use crate::attribute::Attribute;
 use crate::reader::DatasetMetadata;
@@ -9,7 +11,7 @@ use crate::training_example::TrainingExample;
 
 #[derive(Clone, Derivative)]
 #[derivative(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct Hypothesis<'a> {
+pub struct Hypothesis {
     pub attributes: Vec<Attribute>,
 
     #[derivative(
@@ -18,11 +20,13 @@ pub struct Hypothesis<'a> {
         PartialOrd = "ignore",
         Hash = "ignore"
     )]
-    pub dataset_metadata: &'a DatasetMetadata,
+    /// Shared between the hypotheses of a dataset rather than borrowed, so they can be returned,
+    /// stored and sent across threads independently of where the metadata was loaded
+    pub dataset_metadata: Arc<DatasetMetadata>,
 }
 
 // FIXME: Should hypothesis be renamed to training example? Actual hypotheses all seem to be true
-impl<'a> Hypothesis<'a> {
+impl Hypothesis {
     // pub fn enumerate_hypotheses(
     //     _specific_boundary: Hypothesis,
     //     _general_hypotheses: Vec<Hypothesis>,
@@ -38,17 +42,17 @@ impl<'a> Hypothesis<'a> {
     //     todo!()
     // }
 
-    pub fn general(n_attributes: usize, dataset_metadata: &'a DatasetMetadata) -> Self {
+    pub fn general(n_attributes: usize, dataset_metadata: &Arc<DatasetMetadata>) -> Self {
         Self {
             attributes: vec![Attribute::Any; n_attributes],
-            dataset_metadata,
+            dataset_metadata: Arc::clone(dataset_metadata),
         }
     }
 
-    pub fn specific(n_attributes: usize, dataset_metadata: &'a DatasetMetadata) -> Self {
+    pub fn specific(n_attributes: usize, dataset_metadata: &Arc<DatasetMetadata>) -> Self {
         Self {
             attributes: vec![Attribute::NoValue; n_attributes],
-            dataset_metadata,
+            dataset_metadata: Arc::clone(dataset_metadata),
         }
     }
 
@@ -101,9 +105,15 @@ impl<'a> Hypothesis<'a> {
     }
 
     pub fn is_more_specific(&self, other: &Self) -> bool {
+        self.is_more_specific_than(&other.attributes)
+    }
+
+    /// [`Hypothesis::is_more_specific`] for a bare attribute chain, such as one interned in a
+    /// [`crate::HypothesisArena`]
+    pub fn is_more_specific_than(&self, attributes: &[Attribute]) -> bool {
         self.attributes
             .iter()
-            .zip(other.attributes.iter())
+            .zip(attributes.iter())
             .all(|(attribute, other_attribute)| {
                 if let (Attribute::Value(left), Attribute::Value(right)) =
                     (attribute, other_attribute)
@@ -140,7 +150,7 @@ impl<'a> Hypothesis<'a> {
             .collect::<Vec<Attribute>>();
         Self {
             attributes,
-            dataset_metadata: self.dataset_metadata,
+            dataset_metadata: Arc::clone(&self.dataset_metadata),
         }
     }
 
@@ -165,7 +175,7 @@ impl<'a> Hypothesis<'a> {
                     new_attributes[index] = specialization;
                     hypotheses.push(Hypothesis {
                         attributes: new_attributes,
-                        dataset_metadata: self.dataset_metadata,
+                        dataset_metadata: Arc::clone(&self.dataset_metadata),
                     })
                 }
             }
@@ -174,6 +184,32 @@ impl<'a> Hypothesis<'a> {
         hypotheses
     }
 
+    /// The least general hypothesis that is more general than both hypotheses
+    pub fn join(&self, other: &Self) -> Self {
+        Self {
+            attributes: self
+                .attributes
+                .iter()
+                .zip(other.attributes.iter())
+                .map(|(attribute, other_attribute)| attribute.join(other_attribute))
+                .collect(),
+            dataset_metadata: Arc::clone(&self.dataset_metadata),
+        }
+    }
+
+    /// The most general hypothesis that is more specific than both hypotheses
+    pub fn meet(&self, other: &Self) -> Self {
+        Self {
+            attributes: self
+                .attributes
+                .iter()
+                .zip(other.attributes.iter())
+                .map(|(attribute, other_attribute)| attribute.meet(other_attribute))
+                .collect(),
+            dataset_metadata: Arc::clone(&self.dataset_metadata),
+        }
+    }
+
     pub fn to_vec(self) -> Vec<String> {
         // Convert each of the attributes from Attribute type to a string
         self.attributes
@@ -193,7 +229,7 @@ impl<'a> Hypothesis<'a> {
 
     pub fn from_str(
         record: &str,
-        dataset_metadata: &'static DatasetMetadata,
+        dataset_metadata: &Arc<DatasetMetadata>,
     ) -> Result<Self, ParseBoolError> {
         let record: Vec<&str> = record.split(',').map(|str| str.trim()).collect();
         let attributes: Vec<Attribute> = record
@@ -204,18 +240,26 @@ impl<'a> Hypothesis<'a> {
 
         Ok(Hypothesis {
             attributes,
-            dataset_metadata,
+            dataset_metadata: Arc::clone(dataset_metadata),
         })
     }
 }
 
-impl Display for Hypothesis<'_> {
+/// Only the attributes are serialized, the dataset metadata has to be stored alongside the
+/// hypothesis separately (e.g. in a [`crate::Model`])
+impl Serialize for Hypothesis {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.attributes.serialize(serializer)
+    }
+}
+
+impl Display for Hypothesis {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let attributes = self
             .attributes
             .iter()
             .enumerate()
-            .map(|(index, attribute)| attribute.to_valued_string(index, self.dataset_metadata))
+            .map(|(index, attribute)| attribute.to_valued_string(index, &self.dataset_metadata))
             .collect::<Vec<String>>()
             .join(", ");
 
@@ -230,17 +274,19 @@ mod tests {
     use super::*;
 
     lazy_static! {
-        static ref DATASET_METADATA: DatasetMetadata = {
-            serde_yaml::from_reader(std::fs::File::open("./data/enjoysport/metadata.yaml").unwrap())
-                .unwrap()
-        };
+        static ref DATASET_METADATA: Arc<DatasetMetadata> = Arc::new(
+            serde_yaml::from_reader(
+                std::fs::File::open("./data/enjoysport/metadata.yaml").unwrap()
+            )
+            .unwrap()
+        );
     }
 
     #[test]
     fn test_row_to_string() {
         let hypothesis = Hypothesis {
             attributes: vec![Attribute::NoValue, Attribute::Any, Attribute::Value(0)],
-            dataset_metadata: &DATASET_METADATA,
+            dataset_metadata: Arc::clone(&DATASET_METADATA),
         };
 
         let expected = "⟨∅, ?, Foo⟩";
@@ -278,7 +324,7 @@ mod tests {
             general,
             Hypothesis {
                 attributes: vec![Attribute::Any; 5],
-                dataset_metadata: &DATASET_METADATA
+                dataset_metadata: Arc::clone(&DATASET_METADATA)
             }
         );
     }
@@ -290,7 +336,7 @@ mod tests {
             general,
             Hypothesis {
                 attributes: vec![Attribute::NoValue; 5],
-                dataset_metadata: &DATASET_METADATA
+                dataset_metadata: Arc::clone(&DATASET_METADATA)
             }
         );
     }
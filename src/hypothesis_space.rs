@@ -1,112 +1,620 @@
-// use std::collections::HashMap;
-// use std::fmt::Display;
-//
-// use crate::{Attribute, Hypothesis};
-//
-// #[derive(Debug)]
-// pub struct HypothesisSpace {
-//     children: HashMap<Attribute, HypothesisSpace>,
-//     // index: usize,
-// }
-//
-// // impl Iterator for HypothesisSpace {
-// //     type Item = Hypothesis;
-// //
-// //     fn next(&mut self) -> Option<Self::Item> {
-// //         let key = self.children.keys().skip(self.index).next();
-// //
-// //         if let Some(key) = key {
-// //             let children = self.children[key];
-// //         } else {
-// //             None
-// //         }
-// //     }
-// // }
-// //
-// impl HypothesisSpace {
-//     pub fn new() -> Self {
-//         Self {
-//             children: HashMap::new(),
-//         }
-//     }
-//
-//     pub fn insert(&mut self, hypothesis: Hypothesis) {
-//         self.insert_attribute_chain(hypothesis.attributes)
-//     }
-//
-//     pub fn get_hypotheses(self) -> Vec<Hypothesis> {
-//         self.get_attribute_chains()
-//             .into_iter()
-//             .map(|chain| Hypothesis { attributes: chain })
-//             .collect()
-//     }
-//
-//     fn insert_attribute_chain(&mut self, mut attributes: Vec<Attribute>) {
-//         if attributes.is_empty() {
-//             return;
-//         }
-//
-//         let current_attribute = attributes.remove(0);
-//
-//         let node = self
-//             .children
-//             .entry(current_attribute)
-//             .or_insert_with(HypothesisSpace::new);
-//
-//         node.insert_attribute_chain(attributes)
-//     }
-//
-//     fn get_attribute_chains(self) -> Vec<Vec<Attribute>> {
-//         let mut attributes: Vec<Vec<Attribute>> = vec![];
-//
-//         for (attribute, child_node) in self.children.into_iter() {
-//             let mut child_attributes = child_node.get_attribute_chains();
-//
-//             if child_attributes.is_empty() {
-//                 attributes.push(vec![attribute]);
-//                 continue;
-//             }
-//
-//             for child_attribute in child_attributes.iter_mut() {
-//                 child_attribute.insert(0, attribute.clone())
-//             }
-//
-//             attributes.extend(child_attributes);
-//         }
-//
-//         attributes
-//     }
-// }
-//
-// impl Default for HypothesisSpace {
-//     fn default() -> Self {
-//         Self::new()
-//     }
-// }
-//
-// impl Display for HypothesisSpace {
-//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-//         todo!()
-//     }
-// }
-//
-// #[cfg(test)]
-// mod tests {
-//     use std::str::FromStr;
-//
-//     use crate::Hypothesis;
-//
-//     use super::*;
-//
-//     #[test]
-//     fn test_attribute_tree() {
-//         let mut tree = HypothesisSpace::new();
-//
-//         let hypothesis_a = Hypothesis::from_str("?,?,?,?,Cool,?").unwrap();
-//         let hypothesis_b = Hypothesis::from_str("?,?,?,?,?,Same").unwrap();
-//         tree.insert(hypothesis_a);
-//         tree.insert(hypothesis_b);
-//
-//         println!("{:?}", tree.get_hypotheses())
-//     }
-// }
+use std::collections::btree_map;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use crate::reader::DatasetMetadata;
+use crate::{Attribute, Hypothesis, HypothesisArena, HypothesisId, TrainingExample};
+
+/// A set of hypotheses stored as a prefix tree over their attribute chains, so hypotheses that
+/// share leading attributes share nodes.
+///
+/// Queries for the members that are more general or more specific than a hypothesis, or that cover
+/// a training example, only descend into the children whose attribute can match at that depth,
+/// rather than comparing against every member in turn. This makes it suitable for holding a
+/// general boundary that is pruned down to its maximally general hypotheses as it is refined.
+#[derive(Clone, Debug)]
+pub struct HypothesisSpace {
+    root: Node,
+    len: usize,
+    dataset_metadata: Arc<DatasetMetadata>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    children: BTreeMap<Attribute, Node>,
+}
+
+/// Whether `attribute` accepts every value `other` does, as per [`Hypothesis::is_more_general`]
+fn is_more_general(attribute: &Attribute, other: &Attribute) -> bool {
+    match (attribute, other) {
+        (Attribute::Value(left), Attribute::Value(right)) => left == right,
+        _ => attribute >= other,
+    }
+}
+
+/// Whether `other` accepts every value `attribute` does
+fn is_more_specific(attribute: &Attribute, other: &Attribute) -> bool {
+    is_more_general(other, attribute)
+}
+
+impl HypothesisSpace {
+    pub fn new(dataset_metadata: &Arc<DatasetMetadata>) -> Self {
+        Self {
+            root: Node::default(),
+            len: 0,
+            dataset_metadata: Arc::clone(dataset_metadata),
+        }
+    }
+
+    /// A space holding only the maximally general of `hypotheses`, once each
+    pub fn maximal<I>(hypotheses: I, dataset_metadata: &Arc<DatasetMetadata>) -> Self
+    where
+        I: IntoIterator<Item = Hypothesis>,
+    {
+        let mut space = Self::new(dataset_metadata);
+        for hypothesis in hypotheses {
+            space.insert_maximal(hypothesis);
+        }
+        space
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = Node::default();
+        self.len = 0;
+    }
+
+    /// Add a hypothesis. Returns false if it was already a member.
+    pub fn insert(&mut self, hypothesis: Hypothesis) -> bool {
+        self.insert_chain(&hypothesis.attributes)
+    }
+
+    fn insert_chain(&mut self, attributes: &[Attribute]) -> bool {
+        let mut node = &mut self.root;
+        let mut is_new = false;
+        for attribute in attributes {
+            node = node.children.entry(attribute.clone()).or_insert_with(|| {
+                is_new = true;
+                Node::default()
+            });
+        }
+
+        if is_new {
+            self.len += 1;
+        }
+        is_new
+    }
+
+    /// Add a hypothesis unless another member is more general than or equal to it, removing the
+    /// members it is more general than. Returns whether it was added.
+    pub fn insert_maximal(&mut self, hypothesis: Hypothesis) -> bool {
+        self.insert_maximal_chain(&hypothesis.attributes)
+    }
+
+    /// Add each hypothesis in `arena` as per [`HypothesisSpace::insert_maximal`]
+    pub fn extend_maximal(&mut self, arena: &HypothesisArena) {
+        for id in arena.ids() {
+            self.insert_maximal_chain(arena.get(id));
+        }
+    }
+
+    /// Add each hypothesis in `arena` as per [`HypothesisSpace::extend_maximal`], but do the
+    /// subsumption checks in parallel on the current rayon thread pool. Only the insertions and the
+    /// rare removals of members subsumed by a new hypothesis are done sequentially.
+    pub fn par_extend_maximal(&mut self, arena: &HypothesisArena) {
+        let ids = arena.ids().collect_vec();
+
+        // Drop the hypotheses that duplicate or are subsumed by a member
+        let candidates = ids
+            .into_par_iter()
+            .filter(|id| {
+                let attributes = arena.get(*id);
+                !self.contains_chain(attributes) && !self.has_more_general_chain(attributes)
+            })
+            .collect::<Vec<HypothesisId>>();
+
+        // Then the ones that are subsumed by another hypothesis in the arena. The arena holds each
+        // hypothesis once, so only strictly more general ones can subsume it.
+        let mut candidate_space = Self::new(&self.dataset_metadata);
+        for id in candidates.iter() {
+            candidate_space.insert_chain(arena.get(*id));
+        }
+        let maximal = candidates
+            .into_par_iter()
+            .filter(|id| !candidate_space.has_more_general_chain(arena.get(*id)))
+            .map(|id| {
+                let subsumes_member = self
+                    .root
+                    .any_chain_matching(arena.get(id), &is_more_specific);
+                (id, subsumes_member)
+            })
+            .collect::<Vec<(HypothesisId, bool)>>();
+
+        for (id, subsumes_member) in maximal {
+            let attributes = arena.get(id);
+            if subsumes_member {
+                self.len -= self.root.remove_matching(attributes, &is_more_specific);
+            }
+            self.insert_chain(attributes);
+        }
+    }
+
+    fn insert_maximal_chain(&mut self, attributes: &[Attribute]) -> bool {
+        if self.contains_chain(attributes) || self.has_more_general_chain(attributes) {
+            return false;
+        }
+
+        // Most additions subsume no member, and finding that out is much cheaper than
+        // retaining the children of every node along the way
+        if self.root.any_chain_matching(attributes, &is_more_specific) {
+            self.len -= self.root.remove_matching(attributes, &is_more_specific);
+        }
+        self.insert_chain(attributes)
+    }
+
+    /// Remove a hypothesis. Returns whether it was a member.
+    pub fn remove(&mut self, hypothesis: &Hypothesis) -> bool {
+        self.remove_chain(&hypothesis.attributes)
+    }
+
+    fn remove_chain(&mut self, attributes: &[Attribute]) -> bool {
+        let is_removed = self.root.remove(attributes);
+        if is_removed {
+            self.len -= 1;
+        }
+        is_removed
+    }
+
+    pub fn contains(&self, hypothesis: &Hypothesis) -> bool {
+        self.contains_chain(&hypothesis.attributes)
+    }
+
+    fn contains_chain(&self, attributes: &[Attribute]) -> bool {
+        let mut node = &self.root;
+        for attribute in attributes.iter() {
+            match node.children.get(attribute) {
+                Some(child) => node = child,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// The members that are more general than or equal to `hypothesis`
+    pub fn more_general(&self, hypothesis: &Hypothesis) -> Vec<Hypothesis> {
+        self.matching(&hypothesis.attributes, |member, attribute| {
+            is_more_general(member, attribute)
+        })
+    }
+
+    /// The members that are more specific than or equal to `hypothesis`
+    pub fn more_specific(&self, hypothesis: &Hypothesis) -> Vec<Hypothesis> {
+        self.matching(&hypothesis.attributes, is_more_specific)
+    }
+
+    /// The members that classify `training_example` as positive
+    pub fn covering(&self, training_example: &TrainingExample) -> Vec<Hypothesis> {
+        self.matching(&training_example.attributes, |member, attribute| {
+            is_more_general(member, attribute)
+        })
+    }
+
+    /// Whether a member other than `hypothesis` itself is more general than or equal to it
+    pub fn has_more_general(&self, hypothesis: &Hypothesis) -> bool {
+        self.has_more_general_chain(&hypothesis.attributes)
+    }
+
+    fn has_more_general_chain(&self, attributes: &[Attribute]) -> bool {
+        self.root.any_matching(attributes, &|member, attribute| {
+            is_more_general(member, attribute)
+        })
+    }
+
+    /// Remove and return the members that classify `training_example` as positive
+    pub fn take_covering(&mut self, training_example: &TrainingExample) -> Vec<Hypothesis> {
+        let covering = self.covering(training_example);
+        for hypothesis in covering.iter() {
+            self.remove(hypothesis);
+        }
+        covering
+    }
+
+    /// Remove the members that classify `training_example` as positive, interning them into
+    /// `arena` instead of building a [`Hypothesis`] for each
+    pub fn take_covering_into(
+        &mut self,
+        training_example: &TrainingExample,
+        arena: &mut HypothesisArena,
+    ) {
+        let previous_len = arena.len();
+        self.root.collect_matching(
+            &training_example.attributes,
+            &|member, attribute| is_more_general(member, attribute),
+            &mut vec![],
+            &mut |chain| {
+                arena.intern(chain);
+            },
+        );
+        for id in arena.ids().skip(previous_len) {
+            self.remove_chain(arena.get(id));
+        }
+    }
+
+    /// Keep only the members that classify `training_example` as positive
+    pub fn retain_covering(&mut self, training_example: &TrainingExample) {
+        if self.is_empty() {
+            return;
+        }
+
+        self.len = self
+            .root
+            .retain_matching(&training_example.attributes, &|member, attribute| {
+                is_more_general(member, attribute)
+            });
+    }
+
+    /// Iterate over the members in ascending order. The children of each node are ordered by
+    /// attribute, so this is the order of [`Hypothesis`]'s `Ord`, the canonical order of
+    /// [`crate::ComputedBoundaries::canonicalize`].
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            stack: vec![self.root.children.iter()],
+            prefix: vec![],
+            n_attributes: self.dataset_metadata.columns.len(),
+            dataset_metadata: &self.dataset_metadata,
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<Hypothesis> {
+        self.iter().collect()
+    }
+
+    fn matching<F>(&self, attributes: &[Attribute], matches: F) -> Vec<Hypothesis>
+    where
+        F: Fn(&Attribute, &Attribute) -> bool,
+    {
+        let mut chains = vec![];
+        self.root
+            .collect_matching(attributes, &matches, &mut vec![], &mut |chain| {
+                chains.push(chain.to_vec())
+            });
+
+        chains
+            .into_iter()
+            .map(|attributes| Hypothesis {
+                attributes,
+                dataset_metadata: Arc::clone(&self.dataset_metadata),
+            })
+            .collect()
+    }
+}
+
+impl Node {
+    fn remove(&mut self, attributes: &[Attribute]) -> bool {
+        let Some((attribute, rest)) = attributes.split_first() else {
+            return true;
+        };
+
+        let Some(child) = self.children.get_mut(attribute) else {
+            return false;
+        };
+        let is_removed = child.remove(rest);
+        if is_removed && child.children.is_empty() {
+            self.children.remove(attribute);
+        }
+        is_removed
+    }
+
+    /// Drop the chains below this node whose attributes don't all match those of `attributes`.
+    /// Returns how many chains are left.
+    fn retain_matching<F>(&mut self, attributes: &[Attribute], matches: &F) -> usize
+    where
+        F: Fn(&Attribute, &Attribute) -> bool,
+    {
+        let Some((attribute, rest)) = attributes.split_first() else {
+            return 1;
+        };
+
+        let mut n_chains = 0;
+        self.children.retain(|member, child| {
+            let n_child_chains = if matches(member, attribute) {
+                child.retain_matching(rest, matches)
+            } else {
+                0
+            };
+            n_chains += n_child_chains;
+            n_child_chains > 0
+        });
+        n_chains
+    }
+
+    /// Remove the chains below this node whose attributes all match those of `attributes`.
+    /// Returns how many were removed.
+    fn remove_matching<F>(&mut self, attributes: &[Attribute], matches: &F) -> usize
+    where
+        F: Fn(&Attribute, &Attribute) -> bool,
+    {
+        let Some((attribute, rest)) = attributes.split_first() else {
+            return 1;
+        };
+
+        let mut n_removed = 0;
+        self.children.retain(|member, child| {
+            if !matches(member, attribute) {
+                return true;
+            }
+            n_removed += child.remove_matching(rest, matches);
+            !rest.is_empty() && !child.children.is_empty()
+        });
+        n_removed
+    }
+
+    /// Collect the chains below this node whose attributes all match those of `attributes`
+    fn collect_matching<F, C>(
+        &self,
+        attributes: &[Attribute],
+        matches: &F,
+        prefix: &mut Vec<Attribute>,
+        collect: &mut C,
+    ) where
+        F: Fn(&Attribute, &Attribute) -> bool,
+        C: FnMut(&[Attribute]),
+    {
+        let Some((attribute, rest)) = attributes.split_first() else {
+            collect(prefix);
+            return;
+        };
+
+        for (member, child) in self.children.iter() {
+            if matches(member, attribute) {
+                prefix.push(member.clone());
+                child.collect_matching(rest, matches, prefix, collect);
+                prefix.pop();
+            }
+        }
+    }
+
+    /// Whether a chain below this node other than `attributes` itself matches it
+    fn any_matching<F>(&self, attributes: &[Attribute], matches: &F) -> bool
+    where
+        F: Fn(&Attribute, &Attribute) -> bool,
+    {
+        let Some((attribute, rest)) = attributes.split_first() else {
+            return false;
+        };
+
+        self.children.iter().any(|(member, child)| {
+            if member == attribute {
+                child.any_matching(rest, matches)
+            } else {
+                matches(member, attribute) && child.any_chain_matching(rest, matches)
+            }
+        })
+    }
+
+    /// Whether any chain below this node matches `attributes`
+    fn any_chain_matching<F>(&self, attributes: &[Attribute], matches: &F) -> bool
+    where
+        F: Fn(&Attribute, &Attribute) -> bool,
+    {
+        let Some((attribute, rest)) = attributes.split_first() else {
+            return true;
+        };
+
+        self.children.iter().any(|(member, child)| {
+            matches(member, attribute) && child.any_chain_matching(rest, matches)
+        })
+    }
+}
+
+/// Iterates over the hypotheses in a [`HypothesisSpace`] in lexicographic order of their
+/// attributes
+pub struct Iter<'s> {
+    stack: Vec<btree_map::Iter<'s, Attribute, Node>>,
+    prefix: Vec<Attribute>,
+    n_attributes: usize,
+    dataset_metadata: &'s Arc<DatasetMetadata>,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Hypothesis;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some((attribute, child)) = self.stack.last_mut()?.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            self.prefix.truncate(self.stack.len() - 1);
+            self.prefix.push(attribute.clone());
+            if self.prefix.len() == self.n_attributes {
+                return Some(Hypothesis {
+                    attributes: self.prefix.clone(),
+                    dataset_metadata: Arc::clone(self.dataset_metadata),
+                });
+            }
+            self.stack.push(child.children.iter());
+        }
+    }
+}
+
+impl Extend<Hypothesis> for HypothesisSpace {
+    fn extend<T: IntoIterator<Item = Hypothesis>>(&mut self, hypotheses: T) {
+        for hypothesis in hypotheses {
+            self.insert(hypothesis);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::DATASET_METADATA;
+
+    fn hypothesis(record: &str) -> Hypothesis {
+        Hypothesis::from_str(record, &DATASET_METADATA).unwrap()
+    }
+
+    #[test]
+    fn test_insert_shares_prefixes_and_deduplicates() {
+        let mut space = HypothesisSpace::new(&DATASET_METADATA);
+        assert!(space.insert(hypothesis("Sunny,?,?,?,Cool,?")));
+        assert!(space.insert(hypothesis("Sunny,?,?,?,?,Same")));
+        assert!(!space.insert(hypothesis("Sunny,?,?,?,Cool,?")));
+
+        assert_eq!(space.len(), 2);
+        assert_eq!(space.root.children.len(), 1);
+        assert!(space.contains(&hypothesis("Sunny,?,?,?,?,Same")));
+        assert_eq!(
+            space.to_vec(),
+            vec![
+                hypothesis("Sunny,?,?,?,Cool,?"),
+                hypothesis("Sunny,?,?,?,?,Same")
+            ]
+        );
+
+        assert!(space.remove(&hypothesis("Sunny,?,?,?,Cool,?")));
+        assert!(!space.remove(&hypothesis("Sunny,?,?,?,Cool,?")));
+        assert_eq!(space.to_vec(), vec![hypothesis("Sunny,?,?,?,?,Same")]);
+    }
+
+    #[test]
+    fn test_queries() {
+        let mut space = HypothesisSpace::new(&DATASET_METADATA);
+        space.extend([
+            hypothesis("Sunny,?,?,?,?,?"),
+            hypothesis("Sunny,Warm,?,?,?,?"),
+            hypothesis("?,Warm,?,?,?,?"),
+            hypothesis("Rainy,?,?,Strong,?,?"),
+        ]);
+
+        assert_eq!(
+            space.more_general(&hypothesis("Sunny,Warm,High,?,?,?")),
+            vec![
+                hypothesis("Sunny,Warm,?,?,?,?"),
+                hypothesis("Sunny,?,?,?,?,?"),
+                hypothesis("?,Warm,?,?,?,?"),
+            ]
+        );
+        assert_eq!(
+            space.more_specific(&hypothesis("Sunny,?,?,?,?,?")),
+            vec![
+                hypothesis("Sunny,Warm,?,?,?,?"),
+                hypothesis("Sunny,?,?,?,?,?"),
+            ]
+        );
+
+        let example = TrainingExample::from_str(
+            "Rainy,Warm,High,Strong,Cool,Change,false",
+            &DATASET_METADATA,
+        )
+        .unwrap();
+        assert_eq!(
+            space.covering(&example),
+            vec![
+                hypothesis("Rainy,?,?,Strong,?,?"),
+                hypothesis("?,Warm,?,?,?,?")
+            ]
+        );
+
+        assert!(space.has_more_general(&hypothesis("Sunny,Warm,?,?,?,?")));
+        assert!(!space.has_more_general(&hypothesis("Sunny,?,?,?,?,?")));
+    }
+
+    #[test]
+    fn test_insert_maximal() {
+        let space = HypothesisSpace::maximal(
+            [
+                hypothesis("Sunny,Warm,?,?,?,?"),
+                hypothesis("?,Warm,?,?,?,?"),
+                hypothesis("Sunny,Warm,High,?,?,?"),
+                hypothesis("?,Warm,?,?,?,?"),
+                hypothesis("Sunny,?,?,?,?,?"),
+            ],
+            &DATASET_METADATA,
+        );
+
+        assert_eq!(
+            space.to_vec(),
+            vec![hypothesis("Sunny,?,?,?,?,?"), hypothesis("?,Warm,?,?,?,?")]
+        );
+    }
+
+    #[test]
+    fn test_take_covering_into_and_extend_maximal() {
+        let mut space = HypothesisSpace::maximal(
+            [
+                hypothesis("Sunny,?,?,?,?,?"),
+                hypothesis("?,Warm,?,?,?,?"),
+                hypothesis("?,?,?,?,?,Same"),
+            ],
+            &DATASET_METADATA,
+        );
+        let example = TrainingExample::from_str(
+            "Sunny,Warm,High,Strong,Cool,Change,false",
+            &DATASET_METADATA,
+        )
+        .unwrap();
+
+        let mut covering = HypothesisArena::new(6);
+        space.take_covering_into(&example, &mut covering);
+        assert_eq!(covering.len(), 2);
+        assert_eq!(space.to_vec(), vec![hypothesis("?,?,?,?,?,Same")]);
+
+        let mut arena = HypothesisArena::new(6);
+        for record in ["?,Warm,?,?,?,Same", "Sunny,?,Normal,?,?,?", "?,?,?,?,?,?"] {
+            arena.intern(&hypothesis(record).attributes);
+        }
+        space.extend_maximal(&arena);
+        assert_eq!(space.to_vec(), vec![hypothesis("?,?,?,?,?,?")]);
+        assert_eq!(space.len(), 1);
+    }
+
+    #[test]
+    fn test_par_extend_maximal_matches_sequential() {
+        let members = [
+            hypothesis("Sunny,?,?,?,?,?"),
+            hypothesis("?,Warm,?,?,?,?"),
+            hypothesis("?,?,?,?,Cool,Same"),
+        ];
+        let mut arena = HypothesisArena::new(6);
+        for record in [
+            "Sunny,Warm,?,?,?,?",
+            "?,Warm,?,?,?,?",
+            "?,?,?,?,?,Same",
+            "?,?,?,?,Cool,Same",
+            "Rainy,?,?,Strong,?,?",
+            "Rainy,?,?,?,?,?",
+        ] {
+            arena.intern(&hypothesis(record).attributes);
+        }
+
+        let mut sequential = HypothesisSpace::maximal(members.clone(), &DATASET_METADATA);
+        sequential.extend_maximal(&arena);
+        let mut parallel = HypothesisSpace::maximal(members, &DATASET_METADATA);
+        parallel.par_extend_maximal(&arena);
+
+        assert_eq!(parallel.to_vec(), sequential.to_vec());
+        assert_eq!(parallel.len(), sequential.len());
+        assert_eq!(
+            parallel.to_vec(),
+            vec![
+                hypothesis("Sunny,?,?,?,?,?"),
+                hypothesis("Rainy,?,?,?,?,?"),
+                hypothesis("?,Warm,?,?,?,?"),
+                hypothesis("?,?,?,?,?,Same"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::error::Error;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
+
+use log::{error, info, warn};
+
+use crate::{Budget, BudgetExceeded, Checkpointer, ComputedBoundaries, TrainingExample};
+
+/// Whether an interrupt flag has been raised
+pub(crate) fn is_interrupted(interrupt: &Option<Arc<AtomicBool>>) -> bool {
+    interrupt
+        .as_ref()
+        .is_some_and(|interrupt| interrupt.load(std::sync::atomic::Ordering::SeqCst))
+}
+
+/// The boundaries a candidate elimination solver refines as [`learn`] feeds it training examples,
+/// wherever it keeps them. `E` is the error type of the stream of training examples, which the
+/// learner's own errors are returned as.
+pub(crate) trait Learner<E> {
+    /// The number of hypotheses currently held in the general boundary
+    fn general_boundary_len(&self) -> usize;
+
+    /// Refine the boundaries so that every hypothesis they enclose is consistent with the example
+    fn add_example(&mut self, example: TrainingExample) -> Result<(), E>;
+
+    /// Check the general boundary against a budget for a solve that started at `started` and has
+    /// processed `n_examples` training examples. See [`Budget::enforce`].
+    fn enforce_budget(
+        &mut self,
+        budget: &Budget,
+        started: Instant,
+        n_examples: usize,
+    ) -> Result<bool, BudgetExceeded>;
+
+    /// Save the boundaries as they currently are
+    fn save_checkpoint(
+        &self,
+        checkpointer: &Checkpointer,
+        is_approximate: bool,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Consume the learner, returning its specific and general boundaries
+    fn into_boundaries(self) -> Result<ComputedBoundaries, E>;
+}
+
+/// Feed a stream of training examples to `learner` one at a time. Returns the first error in the
+/// stream or from the learner.
+///
+/// If a [`Checkpointer`] is given, checkpoints are saved while learning and when interrupted.
+/// Failing to save a checkpoint is logged rather than aborting the solve.
+///
+/// If the interrupt flag is raised, learning stops once the current training example has been
+/// processed and the boundaries learned so far are returned, flagged as interrupted. The same
+/// happens when the [`Budget`] is exceeded without a beam to fall back on.
+pub(crate) fn learn<L, I, E>(
+    mut learner: L,
+    training_examples: I,
+    mut checkpointer: Option<&mut Checkpointer>,
+    interrupt: &Option<Arc<AtomicBool>>,
+    budget: &Budget,
+) -> Result<ComputedBoundaries, E>
+where
+    L: Learner<E>,
+    I: IntoIterator<Item = Result<TrainingExample, E>>,
+{
+    let training_examples = training_examples.into_iter();
+    let n_training_examples = match training_examples.size_hint() {
+        (_, Some(upper)) => upper.to_string(),
+        (_, None) => "?".to_string(),
+    };
+
+    let started = Instant::now();
+    let mut interrupted_after = None;
+    let mut budget_exceeded = None;
+    let mut is_approximate = false;
+    for (index, example) in training_examples.enumerate() {
+        let example = example?;
+
+        info!(
+            "{index}/{n_training_examples} | {} general hypotheses",
+            learner.general_boundary_len()
+        );
+
+        let is_checkpoint_due = checkpointer
+            .as_deref_mut()
+            .is_some_and(|checkpointer| checkpointer.record(&example));
+
+        learner.add_example(example)?;
+
+        match learner.enforce_budget(budget, started, index + 1) {
+            Ok(is_narrowed) => is_approximate |= is_narrowed,
+            Err(exceeded) => {
+                warn!("{exceeded}");
+                budget_exceeded = Some(exceeded);
+                break;
+            }
+        }
+
+        // A checkpoint is also saved when interrupted, so that continuing from it doesn't redo the
+        // training examples processed since the last one
+        let is_interrupted = is_interrupted(interrupt);
+        if let (true, Some(checkpointer)) =
+            (is_checkpoint_due || is_interrupted, checkpointer.as_deref())
+        {
+            if let Err(err) = learner.save_checkpoint(checkpointer, is_approximate) {
+                error!("Failed to save checkpoint: {err}");
+            }
+        }
+
+        if is_interrupted {
+            info!("Interrupted after {} training examples", index + 1);
+            interrupted_after = Some(index + 1);
+            break;
+        }
+    }
+
+    Ok(ComputedBoundaries {
+        interrupted_after,
+        budget_exceeded,
+        is_approximate,
+        ..learner.into_boundaries()?
+    })
+}
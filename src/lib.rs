@@ -8,12 +8,27 @@ mod training_example;
 pub use training_example::TrainingExample;
 
 mod cli;
-pub use cli::Cli;
+pub use cli::{
+    Algorithm, BudgetArgs, CheckpointArgs, Cli, Command, DatasetArgs, InteractiveArgs, LearnArgs,
+    MergeArgs, QueryLearnArgs, SampleArgs, SolverArgs, SuggestArgs, VerifyArgs, WorkerArgs,
+};
 
 mod reader;
-pub use reader::DatasetReader;
+pub use reader::{DatasetMetadata, DatasetReader, InstanceReader};
 
-pub mod hypothesis_space;
+mod writer;
+pub use writer::DatasetWriter;
+
+mod arena;
+pub use arena::{HypothesisArena, HypothesisId};
+
+mod hypothesis_space;
+pub use hypothesis_space::HypothesisSpace;
+
+mod version_space;
+pub use version_space::VersionSpace;
+
+mod learner;
 
 mod solver;
 pub use solver::Solver;
@@ -21,8 +36,55 @@ pub use solver::Solver;
 mod concurrent;
 pub use concurrent::ConcurrentSolver;
 
-#[derive(Default)]
-pub struct ComputedBoundaries<'a> {
-    pub specific_boundary: Option<Hypothesis<'a>>,
-    pub general_boundary: Vec<Hypothesis<'a>>,
-}
+mod find_s;
+pub use find_s::FindS;
+
+mod find_g;
+pub use find_g::FindG;
+
+mod list_then_eliminate;
+pub use list_then_eliminate::{ListThenEliminate, DEFAULT_MAX_HYPOTHESES};
+
+mod packed;
+pub use packed::{PackedHypothesis, Packing};
+
+mod subsumption;
+pub use subsumption::{retain_maximal, SubsumptionIndex};
+
+mod boundaries;
+pub use boundaries::ComputedBoundaries;
+
+mod merge;
+
+mod check;
+pub use check::Violation;
+
+mod shard;
+pub use shard::ShardedSolver;
+
+mod budget;
+pub use budget::{BeamHeuristic, Budget, BudgetExceeded, Limit};
+
+mod disk_boundary;
+pub use disk_boundary::{DiskBoundary, DEFAULT_CHUNK_SIZE};
+
+mod checkpoint;
+pub use checkpoint::Checkpointer;
+
+mod model;
+pub use model::{DatasetFingerprint, Model, MODEL_FORMAT_VERSION};
+
+mod sampler;
+pub use sampler::HypothesisSampler;
+
+mod query;
+pub use query::{Query, QueryGenerator};
+
+mod oracle;
+pub use oracle::{CommandOracle, Oracle, TerminalOracle};
+
+mod verify;
+pub use verify::{random_dataset, verify_solvers};
+
+#[cfg(test)]
+mod test_fixtures;
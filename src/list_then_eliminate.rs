@@ -0,0 +1,269 @@
+use std::error::Error;
+use std::sync::Arc;
+
+use crate::reader::DatasetMetadata;
+use crate::{retain_maximal, Attribute, ComputedBoundaries, Hypothesis, TrainingExample};
+
+/// The largest hypothesis space [`ListThenEliminate`] enumerates by default
+pub const DEFAULT_MAX_HYPOTHESES: usize = 1 << 20;
+
+/// Learns the boundaries of a dataset by brute force: every conjunctive hypothesis the dataset
+/// metadata allows is listed, those inconsistent with a training example are eliminated, and the
+/// boundaries are the minimal and maximal elements of the hypotheses that remain.
+///
+/// Each attribute of a hypothesis is either one of its values or any value, so the hypothesis
+/// space grows exponentially with the number of attributes. The one exception is the empty
+/// hypothesis [`Hypothesis::specific`], which stands for every hypothesis requiring no value of
+/// some attribute as they all classify every instance as negative. This is only feasible for small
+/// attribute spaces such as `data/enjoysport`, where it serves as a reference implementation that
+/// [`crate::Solver`] can be checked against.
+#[derive(Clone, Debug)]
+pub struct ListThenEliminate {
+    /// Refuse to solve if the hypothesis space holds more hypotheses than this
+    pub max_hypotheses: usize,
+    training_examples: Vec<TrainingExample>,
+    dataset_metadata: Arc<DatasetMetadata>,
+}
+
+impl ListThenEliminate {
+    pub fn new(
+        training_examples: Vec<TrainingExample>,
+        dataset_metadata: &Arc<DatasetMetadata>,
+    ) -> Self {
+        Self {
+            max_hypotheses: DEFAULT_MAX_HYPOTHESES,
+            training_examples,
+            dataset_metadata: Arc::clone(dataset_metadata),
+        }
+    }
+
+    /// How many hypotheses the dataset metadata allows, or `None` if that overflows a `usize`
+    pub fn n_hypotheses(dataset_metadata: &DatasetMetadata) -> Option<usize> {
+        dataset_metadata
+            .columns
+            .iter()
+            .try_fold(1usize, |n_hypotheses, values| {
+                n_hypotheses.checked_mul(values.len() + 1)
+            })?
+            .checked_add(1)
+    }
+
+    /// Every hypothesis consistent with the training examples, in ascending order
+    pub fn version_space(&self) -> Result<Vec<Hypothesis>, Box<dyn Error>> {
+        match Self::n_hypotheses(&self.dataset_metadata) {
+            Some(n_hypotheses) if n_hypotheses <= self.max_hypotheses => {}
+            _ => {
+                return Err(format!(
+                "The hypothesis space is too large to enumerate, it holds more than {} hypotheses",
+                self.max_hypotheses
+            )
+                .into())
+            }
+        }
+
+        let choices = self
+            .dataset_metadata
+            .columns
+            .iter()
+            .map(|values| {
+                (0..values.len())
+                    .map(|value| u8::try_from(value).map(Attribute::Value))
+                    .chain(std::iter::once(Ok(Attribute::Any)))
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<Attribute>>, _>>()
+            .map_err(|_| {
+                format!(
+                    "An attribute can't take more than {} values",
+                    usize::from(u8::MAX) + 1
+                )
+            })?;
+
+        let is_consistent = |hypothesis: &Hypothesis| {
+            self.training_examples
+                .iter()
+                .all(|training_example| hypothesis.is_consistent(training_example))
+        };
+
+        let mut version_space = vec![];
+        let empty = Hypothesis::specific(choices.len(), &self.dataset_metadata);
+        if is_consistent(&empty) {
+            version_space.push(empty);
+        }
+
+        // Count through every combination of choices like an odometer, the last attribute
+        // turning fastest
+        let mut positions = vec![0; choices.len()];
+        loop {
+            let hypothesis = Hypothesis {
+                attributes: positions
+                    .iter()
+                    .zip(choices.iter())
+                    .map(|(position, choices)| choices[*position].clone())
+                    .collect(),
+                dataset_metadata: Arc::clone(&self.dataset_metadata),
+            };
+            if is_consistent(&hypothesis) {
+                version_space.push(hypothesis);
+            }
+
+            let Some(index) = (0..choices.len())
+                .rev()
+                .find(|index| positions[*index] + 1 < choices[*index].len())
+            else {
+                break;
+            };
+            positions[index] += 1;
+            positions[index + 1..].fill(0);
+        }
+
+        Ok(version_space)
+    }
+
+    /// Learn the boundaries of the version space. The specific boundary is missing if no
+    /// hypothesis is consistent with the training examples.
+    pub fn solve(self) -> Result<ComputedBoundaries, Box<dyn Error>> {
+        let version_space = self.version_space()?;
+
+        let specific_boundary = version_space
+            .iter()
+            .find(|hypothesis| {
+                !version_space
+                    .iter()
+                    .any(|other| other != *hypothesis && hypothesis.is_more_general(other))
+            })
+            .cloned();
+
+        let mut general_boundary = version_space;
+        retain_maximal(&mut general_boundary);
+
+        let mut boundaries = ComputedBoundaries {
+            specific_boundary,
+            general_boundary,
+            ..Default::default()
+        };
+        boundaries.canonicalize();
+
+        Ok(boundaries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::Solver;
+
+    #[test]
+    fn test_enjoysport_matches_solver() {
+        let training_examples = enjoysport_examples();
+
+        let expected = Solver::new(training_examples.clone(), &DATASET_METADATA).solve();
+        let actual = ListThenEliminate::new(training_examples, &DATASET_METADATA)
+            .solve()
+            .unwrap();
+
+        assert_eq!(actual.specific_boundary, expected.specific_boundary);
+        assert_eq!(actual.general_boundary, expected.general_boundary);
+    }
+
+    #[test]
+    fn test_rejects_large_hypothesis_spaces() {
+        let mut solver = ListThenEliminate::new(vec![], &DATASET_METADATA);
+        assert_eq!(
+            ListThenEliminate::n_hypotheses(&DATASET_METADATA),
+            Some(4 * 3 * 3 * 3 * 3 * 3 + 1)
+        );
+
+        solver.max_hypotheses = 100;
+        assert!(solver.solve().is_err());
+    }
+
+    #[test]
+    fn test_rejects_attributes_with_too_many_values() {
+        let metadata = Arc::new(DatasetMetadata::new(
+            vec![(0..257).map(|value| format!("v{value}")).collect()],
+            None,
+            None,
+            ',',
+        ));
+
+        assert!(ListThenEliminate::new(vec![], &metadata).solve().is_err());
+    }
+
+    /// Write every hypothesis requiring no value of some attribute as the empty hypothesis, like
+    /// [`ListThenEliminate`] does, e.g. both ⟨?, ∅⟩ and ⟨∅, ?⟩ as ⟨∅, ∅⟩.
+    ///
+    /// The empty hypothesis is then left out of the general boundary. The solver only specializes
+    /// towards it when an attribute has a single value, so once every other hypothesis has been
+    /// eliminated it may either keep it or report the version space as collapsed.
+    fn as_concepts(mut boundaries: ComputedBoundaries) -> ComputedBoundaries {
+        let as_concept = |hypothesis: &mut Hypothesis| {
+            if hypothesis.attributes.contains(&Attribute::NoValue) {
+                hypothesis.attributes.fill(Attribute::NoValue);
+            }
+        };
+
+        boundaries.specific_boundary.iter_mut().for_each(as_concept);
+        boundaries.general_boundary.iter_mut().for_each(as_concept);
+        boundaries
+            .general_boundary
+            .retain(|hypothesis| !hypothesis.attributes.contains(&Attribute::NoValue));
+        boundaries.canonicalize();
+        boundaries
+    }
+
+    /// Metadata with between one and four attributes, each taking between one and three values
+    fn metadata() -> impl Strategy<Value = Arc<DatasetMetadata>> {
+        prop::collection::vec(1..=3usize, 1..=4).prop_map(|n_values| {
+            let columns = n_values
+                .into_iter()
+                .map(|n_values| (0..n_values).map(|value| format!("v{value}")).collect())
+                .collect();
+            Arc::new(DatasetMetadata::new(columns, None, None, ','))
+        })
+    }
+
+    /// Metadata along with up to eight training examples of it, labelled at random. Some values
+    /// are missing, which only a hypothesis requiring any value there covers.
+    fn dataset() -> impl Strategy<Value = (Arc<DatasetMetadata>, Vec<TrainingExample>)> {
+        metadata().prop_flat_map(|metadata| {
+            let example = metadata
+                .columns
+                .iter()
+                .map(|values| {
+                    prop_oneof![
+                        4 => (0..values.len() as u8).prop_map(Attribute::Value),
+                        1 => Just(Attribute::Any),
+                    ]
+                })
+                .collect::<Vec<_>>()
+                .prop_flat_map(|attributes| {
+                    any::<bool>()
+                        .prop_map(move |is_positive| TrainingExample::new(&attributes, is_positive))
+                });
+            (Just(metadata), prop::collection::vec(example, 0..=8))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_solver_matches_list_then_eliminate((metadata, training_examples) in dataset()) {
+            let expected = as_concepts(
+                ListThenEliminate::new(training_examples.clone(), &metadata)
+                    .solve()
+                    .unwrap(),
+            );
+            let actual = as_concepts(Solver::new(training_examples, &metadata).solve());
+
+            prop_assert_eq!(&actual.general_boundary, &expected.general_boundary);
+            // Once the version space has collapsed, the specific boundary the solver is left
+            // with is that of the training examples up to the last positive one
+            if !expected.general_boundary.is_empty() {
+                prop_assert_eq!(actual.specific_boundary, expected.specific_boundary);
+            }
+        }
+    }
+}
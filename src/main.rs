@@ -1,29 +1,355 @@
-use ccelm::Cli;
 use ccelm::ConcurrentSolver;
 use ccelm::DatasetReader;
 use ccelm::Solver;
 use ccelm::TrainingExample;
-use clap::Parser;
+use ccelm::{random_dataset, verify_solvers};
+use ccelm::{
+    Algorithm, Budget, CheckpointArgs, Checkpointer, Cli, Command, CommandOracle,
+    ComputedBoundaries, DatasetArgs, DatasetFingerprint, DatasetMetadata, DatasetWriter, FindG,
+    FindS, HypothesisSpace, InstanceReader, InteractiveArgs, LearnArgs, ListThenEliminate,
+    MergeArgs, Model, Oracle, QueryGenerator, QueryLearnArgs, SampleArgs, ShardedSolver,
+    SolverArgs, SuggestArgs, TerminalOracle, VerifyArgs, VersionSpace, WorkerArgs,
+};
+use clap::{Parser, ValueEnum};
 use itertools::Itertools;
+use log::info;
 use std::error::Error;
+use std::ffi::OsString;
+use std::io::{BufReader, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often the number of training examples read so far is redrawn on the terminal
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
 
 fn main() -> Result<(), Box<dyn Error>> {
     pretty_env_logger::init();
 
     let cli = Cli::parse();
 
-    let reader = DatasetReader::new(cli.dataset, cli.metadata.clone())?;
+    match cli.command {
+        Command::Learn(args) => learn(args),
+        Command::Sample(args) => sample(args),
+        Command::Suggest(args) => suggest(args),
+        Command::Interactive(args) => interactive(args),
+        Command::QueryLearn(args) => query_learn(args),
+        Command::Merge(args) => merge(args),
+        Command::Verify(args) => verify(args),
+        Command::Worker(args) => worker(args),
+    }
+}
 
-    let training_examples = reader.collect::<Result<Vec<TrainingExample>, Box<dyn Error>>>()?;
+/// Load the boundaries and fingerprint of a model to continue learning from, if any
+fn load_resumed(
+    resume: Option<&Path>,
+    metadata: &Arc<DatasetMetadata>,
+) -> Result<(Option<ComputedBoundaries>, DatasetFingerprint), Box<dyn Error>> {
+    match resume {
+        Some(path) => {
+            let model = Model::load(path)?;
+            let fingerprint = model.fingerprint;
+            if model.is_partial {
+                return Err(format!(
+                    "{} is a partial model, learned from only the first {} training examples of its dataset before learning stopped. Continue it on the same dataset with --resume-from instead",
+                    path.display(),
+                    fingerprint.n_examples
+                )
+                .into());
+            }
+            info!(
+                "Resuming from a model learned from {} training examples",
+                fingerprint.n_examples
+            );
+            Ok((Some(model.into_boundaries(metadata)?), fingerprint))
+        }
+        None => Ok((None, DatasetFingerprint::new())),
+    }
+}
 
-    let boundaries = if cli.concurrent {
-        let solver = ConcurrentSolver::new(training_examples, &cli.metadata, cli.threads); //, Some(1));
-        solver.solve()
-    } else {
-        let solver = Solver::new(training_examples, &cli.metadata); //, Some(1));
-        solver.solve()
+/// Learn the boundaries of a dataset, optionally continuing from a previously saved model or
+/// checkpointing as it goes
+fn solve(
+    dataset: &DatasetArgs,
+    solver: &SolverArgs,
+    resume: Option<&Path>,
+    checkpoint: Option<&CheckpointArgs>,
+    budget: Budget,
+) -> Result<(ComputedBoundaries, DatasetFingerprint), Box<dyn Error>> {
+    let metadata = Arc::new(dataset.metadata.clone());
+    let (mut initial_boundaries, mut fingerprint) = load_resumed(resume, &metadata)?;
+
+    let mut reader = DatasetReader::new(&dataset.dataset, dataset.metadata.clone())?;
+
+    if let Some(path) = checkpoint.and_then(|checkpoint| checkpoint.resume_from.as_ref()) {
+        let model = Model::load(path)?;
+
+        // Skip the training examples processed before the checkpoint, making sure they are the
+        // ones the checkpoint was saved after
+        for example in reader.by_ref().take(model.fingerprint.n_examples) {
+            fingerprint.update(&example?);
+        }
+        if fingerprint != model.fingerprint {
+            return Err("The checkpoint was saved while learning a different dataset".into());
+        }
+
+        info!(
+            "Resuming from a checkpoint saved after {} training examples",
+            fingerprint.n_examples
+        );
+        initial_boundaries = Some(model.into_boundaries(&metadata)?);
+    }
+
+    let interrupt = Arc::new(AtomicBool::new(false));
+    {
+        let interrupt = interrupt.clone();
+        ctrlc::set_handler(move || {
+            if interrupt.swap(true, Ordering::SeqCst) {
+                std::process::exit(130);
+            }
+            eprintln!(
+                "\nInterrupted, finishing the current training example. Press Ctrl-C again to exit immediately"
+            );
+        })?;
+    }
+
+    let checkpointer = checkpoint.and_then(|checkpoint| {
+        checkpoint
+            .checkpoint_every
+            .map(|every| Checkpointer::resuming(&checkpoint.checkpoint_path, every, fingerprint))
+    });
+
+    // Report progress as rows arrive rather than buffering the whole dataset up front. The count
+    // is only shown on a terminal, and redrawn at most every PROGRESS_INTERVAL
+    let show_progress = std::io::stderr().is_terminal();
+    let mut progress_shown: Option<Instant> = None;
+    let training_examples = reader.inspect(|example| {
+        if let Ok(example) = example {
+            fingerprint.update(example);
+            if show_progress
+                && progress_shown.is_none_or(|shown| shown.elapsed() >= PROGRESS_INTERVAL)
+            {
+                eprint!("\rProcessing training example {}", fingerprint.n_examples);
+                progress_shown = Some(Instant::now());
+            }
+        }
+    });
+
+    let is_approximate = initial_boundaries
+        .as_ref()
+        .is_some_and(|boundaries| boundaries.is_approximate);
+    let options = SolveOptions {
+        checkpointer,
+        interrupt: Some(interrupt),
+        budget,
     };
+    let boundaries = solve_stream(
+        training_examples,
+        &metadata,
+        solver,
+        initial_boundaries,
+        options,
+    );
+    if show_progress {
+        eprintln!("\rProcessing training example {}", fingerprint.n_examples);
+    }
+
+    let mut boundaries = boundaries?;
+    boundaries.is_approximate |= is_approximate;
+
+    Ok((boundaries, fingerprint))
+}
+
+/// Behaviour of a solve beyond the choice of solver, which only `learn` makes use of
+#[derive(Default)]
+struct SolveOptions {
+    checkpointer: Option<Checkpointer>,
+    interrupt: Option<Arc<AtomicBool>>,
+    budget: Budget,
+}
+
+/// Learn from a stream of training examples with the solver selected on the command line
+fn solve_stream<I>(
+    training_examples: I,
+    metadata: &Arc<DatasetMetadata>,
+    solver_args: &SolverArgs,
+    initial_boundaries: Option<ComputedBoundaries>,
+    options: SolveOptions,
+) -> Result<ComputedBoundaries, Box<dyn Error>>
+where
+    I: IntoIterator<Item = Result<TrainingExample, Box<dyn Error>>>,
+{
+    require_candidate_elimination_options(solver_args)?;
+    let needs_candidate_elimination = initial_boundaries.is_some()
+        || options.checkpointer.is_some()
+        || !options.budget.is_unlimited();
+    if needs_candidate_elimination && solver_args.algorithm != Algorithm::CandidateElimination {
+        return Err(
+            "Resuming, checkpoints and budgets are only supported by candidate elimination".into(),
+        );
+    }
+
+    match solver_args.algorithm {
+        Algorithm::CandidateElimination => {}
+        Algorithm::ListThenEliminate => {
+            let training_examples = training_examples
+                .into_iter()
+                .collect::<Result<Vec<TrainingExample>, Box<dyn Error>>>()?;
+            return ListThenEliminate::new(training_examples, metadata).solve();
+        }
+        Algorithm::FindS => return FindS::new(vec![], metadata).solve_stream(training_examples),
+        Algorithm::FindG => return FindG::new(vec![], metadata).solve_stream(training_examples),
+    }
+
+    if solver_args.concurrent {
+        let mut solver = ConcurrentSolver::new(vec![], metadata, solver_args.threads());
+        if let Some(boundaries) = initial_boundaries {
+            solver.specific_boundary = boundaries
+                .specific_boundary
+                .unwrap_or(solver.specific_boundary);
+            solver.general_boundary =
+                HypothesisSpace::maximal(boundaries.general_boundary, metadata);
+        }
+        solver.checkpointer = options.checkpointer;
+        solver.interrupt = options.interrupt;
+        solver.budget = options.budget;
+        solver.solve_stream(training_examples)
+    } else {
+        let mut solver = Solver::new(vec![], metadata);
+        if let Some(boundaries) = initial_boundaries {
+            solver.specific_boundary = boundaries
+                .specific_boundary
+                .unwrap_or(solver.specific_boundary);
+            solver.general_boundary =
+                HypothesisSpace::maximal(boundaries.general_boundary, metadata);
+        }
+        solver.checkpointer = options.checkpointer;
+        solver.interrupt = options.interrupt;
+        solver.budget = options.budget;
+        match &solver_args.disk_dir {
+            Some(directory) => solver.solve_stream_on_disk(
+                directory,
+                solver_args.disk_chunk_size(),
+                training_examples,
+            ),
+            None => solver.solve_stream(training_examples),
+        }
+    }
+}
+
+/// Learn the boundaries of a dataset by splitting it into shards that are each learned by a
+/// `ccelm worker` process, optionally continuing from a previously saved model
+fn solve_sharded(
+    dataset: &DatasetArgs,
+    solver: &SolverArgs,
+    resume: Option<&Path>,
+    n_shards: usize,
+    max_attempts: usize,
+) -> Result<(ComputedBoundaries, DatasetFingerprint), Box<dyn Error>> {
+    require_candidate_elimination_options(solver)?;
+    let metadata = Arc::new(dataset.metadata.clone());
+    let (initial_boundaries, mut fingerprint) = load_resumed(resume, &metadata)?;
+
+    // Shards are kept in memory so that a failed worker can be sent its shard again
+    let training_examples = DatasetReader::new(&dataset.dataset, dataset.metadata.clone())?
+        .inspect(|example| {
+            if let Ok(example) = example {
+                fingerprint.update(example);
+            }
+        })
+        .collect::<Result<Vec<TrainingExample>, Box<dyn Error>>>()?;
+
+    let mut worker_args: Vec<OsString> = vec!["worker".into()];
+    if solver.concurrent {
+        worker_args.push("--concurrent".into());
+    }
+    if let Some(threads) = solver.threads {
+        worker_args.push(format!("--threads={threads}").into());
+    }
+    if let Some(algorithm) = solver.algorithm.to_possible_value() {
+        worker_args.push(format!("--algorithm={}", algorithm.get_name()).into());
+    }
+    if let Some(disk_dir) = &solver.disk_dir {
+        worker_args.extend(["--disk-dir".into(), disk_dir.into()]);
+    }
+    if let Some(disk_chunk_size) = solver.disk_chunk_size {
+        worker_args.push(format!("--disk-chunk-size={disk_chunk_size}").into());
+    }
+
+    let mut sharded_solver = ShardedSolver::new(
+        training_examples,
+        &metadata,
+        n_shards,
+        std::env::current_exe()?,
+        worker_args,
+    );
+    sharded_solver.max_attempts = max_attempts;
+
+    let mut boundaries = sharded_solver.solve()?;
+    if let Some(initial_boundaries) = initial_boundaries {
+        boundaries = initial_boundaries.merge(&boundaries)?;
+    }
+
+    Ok((boundaries, fingerprint))
+}
+
+fn solve_examples(
+    training_examples: Vec<TrainingExample>,
+    metadata: &Arc<DatasetMetadata>,
+    solver: &SolverArgs,
+) -> Result<ComputedBoundaries, Box<dyn Error>> {
+    require_candidate_elimination_options(solver)?;
+    match solver.algorithm {
+        Algorithm::CandidateElimination if solver.concurrent => {
+            let solver = ConcurrentSolver::new(training_examples, metadata, solver.threads());
+            Ok(solver.solve())
+        }
+        Algorithm::CandidateElimination => {
+            let solver = Solver::new(training_examples, metadata);
+            Ok(solver.solve())
+        }
+        Algorithm::ListThenEliminate => {
+            let solver = ListThenEliminate::new(training_examples, metadata);
+            solver.solve()
+        }
+        Algorithm::FindS => Ok(FindS::new(training_examples, metadata).solve()),
+        Algorithm::FindG => Ok(FindG::new(training_examples, metadata).solve()),
+    }
+}
+
+/// Fail unless the selected algorithm learns the whole version space, which `command` needs
+fn require_version_space(solver: &SolverArgs, command: &str) -> Result<(), Box<dyn Error>> {
+    if solver.algorithm.learns_version_space() {
+        return Ok(());
+    }
+
+    let algorithm = solver.algorithm.to_possible_value().unwrap();
+    Err(format!(
+        "{command} needs the whole version space, which {} doesn't learn",
+        algorithm.get_name()
+    )
+    .into())
+}
 
+/// Fail if options that only the candidate elimination solvers support are given along with
+/// another algorithm, rather than ignoring them
+fn require_candidate_elimination_options(solver: &SolverArgs) -> Result<(), Box<dyn Error>> {
+    let options = solver.candidate_elimination_options();
+    if solver.algorithm == Algorithm::CandidateElimination || options.is_empty() {
+        return Ok(());
+    }
+
+    let algorithm = solver.algorithm.to_possible_value().unwrap();
+    Err(format!(
+        "{} can only be used with candidate elimination, not {}",
+        options.join(", "),
+        algorithm.get_name()
+    )
+    .into())
+}
+
+fn print_boundaries(boundaries: &ComputedBoundaries) {
     println!(
         "Specific Boundary\n{}",
         boundaries
@@ -36,16 +362,476 @@ fn main() -> Result<(), Box<dyn Error>> {
         "General Boundary: {} hypotheses",
         boundaries.general_boundary.len()
     );
+}
+
+fn write_boundaries(path: PathBuf, boundaries: ComputedBoundaries) -> Result<(), Box<dyn Error>> {
+    let mut contents = String::new();
+    if let Some(n_examples) = boundaries.interrupted_after {
+        contents.push_str(&format!(
+            "----------Partial: interrupted after {n_examples} training examples----------\n\n"
+        ));
+    }
+    if let Some(exceeded) = &boundaries.budget_exceeded {
+        contents.push_str(&format!("----------Partial: {exceeded}----------\n\n"));
+    }
+    if boundaries.is_approximate {
+        contents.push_str(
+            "----------Approximate: the general boundary was narrowed to a beam----------\n\n",
+        );
+    }
+    contents.push_str(&format!(
+        "----------Specific Boundary----------\n{}\n\n----------General Boundary----------\n{}",
+        boundaries.specific_boundary.iter().join("\n"),
+        boundaries.general_boundary.into_iter().join("\n")
+    ));
+
+    std::fs::write(path, contents)?;
+
+    Ok(())
+}
+
+fn print_boundary_sizes(boundaries: &ComputedBoundaries) {
+    println!(
+        "Specific Boundary: {} hypotheses | General Boundary: {} hypotheses",
+        boundaries.specific_boundary.iter().count(),
+        boundaries.general_boundary.len()
+    );
+}
+
+/// Check the boundaries against every training example of the dataset, printing each violation.
+/// Returns how many there were.
+fn check_boundaries(
+    boundaries: &ComputedBoundaries,
+    dataset: &DatasetArgs,
+) -> Result<usize, Box<dyn Error>> {
+    let training_examples = DatasetReader::new(&dataset.dataset, dataset.metadata.clone())?
+        .collect::<Result<Vec<TrainingExample>, Box<dyn Error>>>()?;
+
+    let violations = boundaries.verify(&training_examples);
+    for violation in violations.iter() {
+        eprintln!("{violation}");
+    }
+
+    Ok(violations.len())
+}
 
-    if let Some(path) = cli.output_path {
-        let contents = format!(
-            "----------Specific Boundary----------\n{}\n\n----------General Boundary----------\n{}",
-            boundaries.specific_boundary.unwrap(),
-            boundaries.general_boundary.into_iter().join("\n")
+fn learn(args: LearnArgs) -> Result<(), Box<dyn Error>> {
+    if args.check && args.dataset.dataset == Path::new("-") {
+        return Err(
+            "--check reads the dataset again after learning, so it can't be read from stdin".into(),
         );
+    }
+
+    // A model is continued from as a version space, and shards are merged as version spaces, so
+    // neither works with the boundaries of an algorithm that only learns part of it
+    if args.model_path.is_some() {
+        require_version_space(&args.solver, "--model-path")?;
+    }
+    if args.shards.is_some() {
+        require_version_space(&args.solver, "--shards")?;
+    }
+
+    let (boundaries, fingerprint) = match args.shards {
+        Some(n_shards) => solve_sharded(
+            &args.dataset,
+            &args.solver,
+            args.resume.as_deref(),
+            n_shards,
+            args.shard_attempts,
+        )?,
+        None => solve(
+            &args.dataset,
+            &args.solver,
+            args.resume.as_deref(),
+            Some(&args.checkpoint),
+            args.budget.budget(),
+        )?,
+    };
 
-        std::fs::write(path, contents).unwrap();
+    print_boundaries(&boundaries);
+
+    let interrupted_after = boundaries.interrupted_after;
+    if let Some(n_examples) = interrupted_after {
+        eprintln!("Interrupted after {n_examples} training examples, the boundaries are partial");
+    }
+
+    let budget_exceeded = boundaries.budget_exceeded.clone();
+    match args.solver.algorithm {
+        Algorithm::FindS => {
+            eprintln!(
+                "find-s only learns the specific boundary, the general boundary is left empty"
+            )
+        }
+        Algorithm::FindG => eprintln!(
+            "find-g only learns a single general hypothesis, the general boundary is approximate"
+        ),
+        _ if boundaries.is_approximate => {
+            eprintln!("The general boundary was narrowed to stay within budget and is approximate")
+        }
+        _ => {}
+    }
+
+    // Partial boundaries are those of only some of the training examples, so there is nothing to
+    // check them against
+    let n_violations = match (args.check, interrupted_after, &budget_exceeded) {
+        (false, _, _) => 0,
+        (true, Some(n_examples), _) => {
+            eprintln!(
+                "Skipped --check: learning was interrupted, so the boundaries are only those of the first {n_examples} training examples"
+            );
+            0
+        }
+        (true, None, Some(_)) => {
+            eprintln!(
+                "Skipped --check: learning stopped once the budget was exceeded, so the boundaries are only those of some of the training examples"
+            );
+            0
+        }
+        (true, None, None) => {
+            let n_violations = check_boundaries(&boundaries, &args.dataset)?;
+            if n_violations == 0 {
+                println!(
+                    "Checked the boundaries against every training example, no violations found"
+                );
+            }
+            n_violations
+        }
+    };
+
+    if let Some(path) = &args.model_path {
+        boundaries.save(path, &args.dataset.metadata, fingerprint)?;
+    }
+
+    if let Some(path) = args.output_path {
+        write_boundaries(path, boundaries)?;
     }
 
+    if interrupted_after.is_some() {
+        // Both the model and the last checkpoint record how many training examples were processed,
+        // so either can be continued from with the rest of the dataset
+        let checkpoint_path = Some(&args.checkpoint.checkpoint_path)
+            .filter(|path| args.checkpoint.checkpoint_every.is_some() && path.exists());
+        if let Some(path) = args.model_path.as_ref().or(checkpoint_path) {
+            eprintln!(
+                "Continue learning from {} with --resume-from {}",
+                path.display(),
+                path.display()
+            );
+        }
+    }
+
+    if n_violations > 0 {
+        return Err(format!("The learned boundaries failed {n_violations} checks").into());
+    }
+    if interrupted_after.is_some() {
+        std::process::exit(130);
+    }
+    if let Some(exceeded) = budget_exceeded {
+        return Err(exceeded.to_string().into());
+    }
+
+    Ok(())
+}
+
+fn sample(args: SampleArgs) -> Result<(), Box<dyn Error>> {
+    require_version_space(&args.solver, "sample")?;
+    let (boundaries, _) = solve(&args.dataset, &args.solver, None, None, Budget::default())?;
+    if boundaries.interrupted_after.is_some() {
+        return Err("Interrupted before every training example was processed".into());
+    }
+
+    let sampler = boundaries
+        .sampler(args.seed)
+        .ok_or("The version space is empty, there are no hypotheses to sample")?;
+
+    for hypothesis in sampler.take(args.n) {
+        println!("{hypothesis}");
+    }
+
+    Ok(())
+}
+
+fn suggest(args: SuggestArgs) -> Result<(), Box<dyn Error>> {
+    require_version_space(&args.solver, "suggest")?;
+    let (boundaries, _) = solve(&args.dataset, &args.solver, None, None, Budget::default())?;
+    if boundaries.interrupted_after.is_some() {
+        return Err("Interrupted before every training example was processed".into());
+    }
+
+    let mut generator = QueryGenerator::new(&boundaries, args.samples, args.seed)?
+        .ok_or("The version space is empty, there is nothing left to learn")?;
+
+    let query = if let Some(pool) = args.pool {
+        let pool = InstanceReader::new(pool, args.dataset.metadata.clone())?
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+        generator.suggest_from_pool(pool)
+    } else {
+        generator.suggest()
+    };
+
+    match query {
+        Some(query) => {
+            println!(
+                "{}",
+                query
+                    .to_valued_strings(&args.dataset.metadata)
+                    .join(&args.dataset.metadata.delimiter.to_string())
+            );
+            println!(
+                "Estimated {:.1}% of the version space classifies this instance as positive",
+                query.positive_fraction * 100.0
+            );
+        }
+        None => println!("No informative instance found, the version space has converged"),
+    }
+
+    Ok(())
+}
+
+/// Read an optional dataset of initial training examples along with its headers
+fn read_initial_examples(
+    path: Option<&PathBuf>,
+    metadata: &DatasetMetadata,
+) -> Result<(Vec<TrainingExample>, Vec<String>), Box<dyn Error>> {
+    match path {
+        Some(path) => {
+            let mut reader = DatasetReader::new(path, metadata.clone())?;
+            let headers = reader.headers()?;
+            let training_examples =
+                reader.collect::<Result<Vec<TrainingExample>, Box<dyn Error>>>()?;
+            Ok((training_examples, headers))
+        }
+        None => Ok((vec![], DatasetWriter::default_headers(metadata))),
+    }
+}
+
+/// The outcome of repeatedly querying an oracle
+struct QueryOutcome {
+    boundaries: ComputedBoundaries,
+    n_queries: usize,
+    converged: bool,
+}
+
+/// Repeatedly query the oracle for the most informative instance and refine the boundaries with
+/// its answer, until the version space converges or the oracle stops answering
+fn run_queries(
+    boundaries: ComputedBoundaries,
+    oracle: &mut dyn Oracle,
+    mut writer: Option<&mut DatasetWriter>,
+    metadata: &Arc<DatasetMetadata>,
+    n_samples: usize,
+    seed: u64,
+    max_queries: Option<usize>,
+) -> Result<QueryOutcome, Box<dyn Error>> {
+    let mut version_space = VersionSpace::from_boundaries(boundaries, metadata);
+    let mut n_queries = 0;
+    let mut converged = false;
+
+    while max_queries.is_none_or(|max_queries| n_queries < max_queries) {
+        if version_space.is_converged() {
+            converged = true;
+            break;
+        }
+
+        let boundaries = version_space.boundaries();
+        let Some(mut generator) =
+            QueryGenerator::new(&boundaries, n_samples, seed.wrapping_add(n_queries as u64))?
+        else {
+            println!("The version space is empty, the provided labels are inconsistent");
+            break;
+        };
+
+        let Some(query) = generator.suggest() else {
+            converged = true;
+            break;
+        };
+
+        let Some(is_positive) = oracle.label(&query)? else {
+            break;
+        };
+        n_queries += 1;
+
+        let example = query.into_training_example(is_positive);
+        if let Some(writer) = writer.as_mut() {
+            writer.write(&example)?;
+        }
+
+        version_space.add_example(example);
+        print_boundary_sizes(&version_space.boundaries());
+    }
+
+    Ok(QueryOutcome {
+        boundaries: version_space.into_boundaries(),
+        n_queries,
+        converged,
+    })
+}
+
+fn interactive(args: InteractiveArgs) -> Result<(), Box<dyn Error>> {
+    require_version_space(&args.solver, "interactive")?;
+    let metadata = &Arc::new(args.metadata);
+
+    let (training_examples, headers) = read_initial_examples(args.dataset.as_ref(), metadata)?;
+
+    let mut writer = DatasetWriter::new(&args.output_path, metadata, &headers)?;
+    for example in training_examples.iter() {
+        writer.write(example)?;
+    }
+
+    let boundaries = solve_examples(training_examples, metadata, &args.solver)?;
+    print_boundary_sizes(&boundaries);
+
+    let mut oracle = TerminalOracle::new(
+        std::io::stdin().lock(),
+        std::io::stdout(),
+        headers[..metadata.columns.len()].to_vec(),
+        metadata,
+    );
+
+    let outcome = run_queries(
+        boundaries,
+        &mut oracle,
+        Some(&mut writer),
+        metadata,
+        args.samples,
+        args.seed,
+        None,
+    )?;
+
+    if outcome.converged {
+        println!("The version space has converged");
+    }
+    print_boundaries(&outcome.boundaries);
+
+    Ok(())
+}
+
+fn query_learn(args: QueryLearnArgs) -> Result<(), Box<dyn Error>> {
+    require_version_space(&args.solver, "query-learn")?;
+    let metadata = &Arc::new(args.metadata);
+
+    let (training_examples, _) = read_initial_examples(args.dataset.as_ref(), metadata)?;
+
+    let boundaries = solve_examples(training_examples, metadata, &args.solver)?;
+
+    let mut oracle = CommandOracle::new(&args.oracle, &args.oracle_args, metadata);
+
+    let outcome = run_queries(
+        boundaries,
+        &mut oracle,
+        None,
+        metadata,
+        args.samples,
+        args.seed,
+        args.max_queries,
+    )?;
+
+    if outcome.converged {
+        println!("Converged after {} queries", outcome.n_queries);
+    } else {
+        println!(
+            "Stopped after {} queries without converging",
+            outcome.n_queries
+        );
+    }
+    print_boundaries(&outcome.boundaries);
+
+    if let Some(path) = args.output_path {
+        write_boundaries(path, outcome.boundaries)?;
+    }
+
+    Ok(())
+}
+
+fn merge(args: MergeArgs) -> Result<(), Box<dyn Error>> {
+    let models = args
+        .models
+        .iter()
+        .map(Model::load)
+        .collect::<Result<Vec<Model>, Box<dyn Error>>>()?;
+
+    let metadata = Arc::new(models[0].metadata.clone());
+    let mut boundaries = models[0].boundaries();
+    let mut fingerprint = models[0].fingerprint;
+    for model in models.iter().skip(1) {
+        boundaries = boundaries.merge(&model.clone().into_boundaries(&metadata)?)?;
+        fingerprint = fingerprint.combine(&model.fingerprint);
+    }
+
+    print_boundaries(&boundaries);
+
+    if let Some(path) = args.model_path {
+        boundaries.save(path, &metadata, fingerprint)?;
+    }
+
+    if let Some(path) = args.output_path {
+        write_boundaries(path, boundaries)?;
+    }
+
+    Ok(())
+}
+
+fn verify(args: VerifyArgs) -> Result<(), Box<dyn Error>> {
+    if args.dataset.is_none() && args.random == 0 {
+        return Err("Nothing to verify, pass a --dataset and/or --random".into());
+    }
+
+    if let (Some(path), Some(metadata)) = (&args.dataset, args.metadata) {
+        let training_examples = DatasetReader::new(path, metadata.clone())?
+            .take(args.examples.unwrap_or(usize::MAX))
+            .collect::<Result<Vec<TrainingExample>, Box<dyn Error>>>()?;
+
+        let boundaries = verify_solvers(&training_examples, &Arc::new(metadata), &args.threads)
+            .map_err(|err| format!("{}: {err}", path.display()))?;
+        println!(
+            "{}: every solver agrees on {} general hypotheses",
+            path.display(),
+            boundaries.general_boundary.len()
+        );
+    }
+
+    for seed in args.seed..args.seed + args.random as u64 {
+        let (metadata, training_examples) = random_dataset(
+            args.random_attributes,
+            args.random_values,
+            args.random_examples,
+            seed,
+        )?;
+
+        let boundaries = verify_solvers(&training_examples, &Arc::new(metadata), &args.threads)
+            .map_err(|err| format!("Random dataset with seed {seed}: {err}"))?;
+        println!(
+            "Random dataset with seed {seed}: every solver agrees on {} general hypotheses",
+            boundaries.general_boundary.len()
+        );
+    }
+
+    Ok(())
+}
+
+fn worker(args: WorkerArgs) -> Result<(), Box<dyn Error>> {
+    let (metadata, reader) = ShardedSolver::read_shard(BufReader::new(std::io::stdin()))?;
+    let metadata = Arc::new(metadata);
+
+    let mut fingerprint = DatasetFingerprint::new();
+    let training_examples = reader.inspect(|example| {
+        if let Ok(example) = example {
+            fingerprint.update(example);
+        }
+    });
+
+    let boundaries = solve_stream(
+        training_examples,
+        &metadata,
+        &args.solver,
+        None,
+        SolveOptions::default(),
+    )?;
+
+    let model = Model::new(&boundaries, &metadata, fingerprint);
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer(&mut stdout, &model)?;
+    stdout.flush()?;
+
     Ok(())
 }
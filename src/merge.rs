@@ -0,0 +1,116 @@
+use std::error::Error;
+
+use itertools::Itertools;
+
+use crate::{retain_maximal, ComputedBoundaries, Hypothesis};
+
+impl ComputedBoundaries {
+    /// Intersect two version spaces learned over the same dataset metadata, using Hirsh's
+    /// incremental version space merging.
+    ///
+    /// The version space of a set of training examples is the intersection of the version spaces
+    /// of any partition of them, so boundaries learned from separate shards of a dataset can be
+    /// merged into the boundaries that would have been learned from the whole dataset.
+    ///
+    /// The specific boundary of the intersection is the join of both specific boundaries, and
+    /// its general boundary consists of the maximal meets of every pair of general hypotheses
+    /// that are still more general than the new specific boundary. If the version spaces don't
+    /// intersect, the resulting general boundary is empty. The result is approximate if either
+    /// version space is.
+    pub fn merge(&self, other: &Self) -> Result<Self, Box<dyn Error>> {
+        let (Some(specific), Some(other_specific)) =
+            (&self.specific_boundary, &other.specific_boundary)
+        else {
+            return Err("Both boundaries must have a specific boundary to be merged".into());
+        };
+
+        if specific.dataset_metadata != other_specific.dataset_metadata {
+            return Err(
+                "Only boundaries learned using the same dataset metadata can be merged".into(),
+            );
+        }
+
+        let specific_boundary = specific.join(other_specific);
+
+        let is_covered = |boundary: &[Hypothesis]| {
+            boundary
+                .iter()
+                .any(|general| general.is_more_general(&specific_boundary))
+        };
+        if !is_covered(&self.general_boundary) || !is_covered(&other.general_boundary) {
+            return Ok(Self {
+                specific_boundary: Some(specific_boundary),
+                general_boundary: vec![],
+                interrupted_after: None,
+                budget_exceeded: None,
+                is_approximate: self.is_approximate || other.is_approximate,
+            });
+        }
+
+        let mut general_boundary: Vec<Hypothesis> = self
+            .general_boundary
+            .iter()
+            .cartesian_product(other.general_boundary.iter())
+            .map(|(general, other_general)| general.meet(other_general))
+            .filter(|general| general.is_more_general(&specific_boundary))
+            .collect();
+        retain_maximal(&mut general_boundary);
+
+        let mut merged = Self {
+            specific_boundary: Some(specific_boundary),
+            general_boundary,
+            interrupted_after: None,
+            budget_exceeded: None,
+            is_approximate: self.is_approximate || other.is_approximate,
+        };
+        merged.canonicalize();
+
+        Ok(merged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::{Solver, TrainingExample};
+
+    #[test]
+    fn test_merging_shards_matches_learning_everything() {
+        let training_examples = enjoysport_examples();
+        let full = Solver::new(training_examples.clone(), &DATASET_METADATA).solve();
+
+        // Try every way of splitting the examples into two shards
+        for mask in 0..(1 << training_examples.len()) {
+            let (left, right): (Vec<_>, Vec<_>) = training_examples
+                .iter()
+                .cloned()
+                .enumerate()
+                .partition(|(index, _)| mask & (1 << index) != 0);
+
+            let shard = |examples: Vec<(usize, TrainingExample)>| {
+                let examples = examples.into_iter().map(|(_, example)| example).collect();
+                Solver::new(examples, &DATASET_METADATA).solve()
+            };
+
+            let merged = shard(left).merge(&shard(right)).unwrap();
+
+            assert_eq!(merged.specific_boundary, full.specific_boundary);
+            assert!(merged.is_canonical());
+            assert_eq!(merged.general_boundary, full.general_boundary);
+        }
+    }
+
+    #[test]
+    fn test_merging_disjoint_version_spaces() {
+        let positive =
+            TrainingExample::from_str("Sunny,Warm,High,Strong,Cool,Change,true", &DATASET_METADATA)
+                .unwrap();
+        let mut negative = positive.clone();
+        negative.is_positive = false;
+
+        let left = Solver::new(vec![positive], &DATASET_METADATA).solve();
+        let right = Solver::new(vec![negative], &DATASET_METADATA).solve();
+
+        assert!(left.merge(&right).unwrap().general_boundary.is_empty());
+    }
+}
@@ -0,0 +1,396 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::reader::DatasetMetadata;
+use crate::{Attribute, ComputedBoundaries, Hypothesis, TrainingExample};
+
+/// The version of the model file format written by this build. Bumped whenever the format changes
+/// in a way that older builds can't read.
+pub const MODEL_FORMAT_VERSION: u32 = 1;
+
+/// Identifies the sequence of training examples a model was learned from, so that a model can be
+/// matched against the dataset it came from.
+///
+/// The fingerprint is a 64-bit FNV-1a hash over every attribute and label in order. Since the hash
+/// is only ever extended, learning can continue from a saved fingerprint and end up with the same
+/// fingerprint as learning from all of the training examples in one go.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatasetFingerprint {
+    /// How many training examples have been hashed
+    pub n_examples: usize,
+
+    /// The hash of those training examples
+    pub hash: u64,
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+impl DatasetFingerprint {
+    /// The fingerprint of an empty dataset
+    pub fn new() -> Self {
+        Self {
+            n_examples: 0,
+            hash: FNV_OFFSET_BASIS,
+        }
+    }
+
+    pub fn update(&mut self, example: &TrainingExample) {
+        let attributes = example
+            .attributes
+            .iter()
+            .flat_map(|attribute| match attribute {
+                Attribute::NoValue => [0, 0],
+                Attribute::Value(value) => [1, *value],
+                Attribute::Any => [2, 0],
+            });
+
+        for byte in attributes.chain(std::iter::once(u8::from(example.is_positive))) {
+            self.hash = (self.hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+
+        self.n_examples += 1;
+    }
+
+    /// The fingerprint of a model merged from models with these two fingerprints. Unlike
+    /// [`DatasetFingerprint::update`], this doesn't match the fingerprint of learning from the
+    /// concatenated training examples.
+    pub fn combine(&self, other: &Self) -> Self {
+        let mut combined = Self {
+            n_examples: self.n_examples + other.n_examples,
+            hash: FNV_OFFSET_BASIS,
+        };
+
+        for byte in self
+            .hash
+            .to_le_bytes()
+            .into_iter()
+            .chain(other.hash.to_le_bytes())
+        {
+            combined.hash = (combined.hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+
+        combined
+    }
+}
+
+impl Default for DatasetFingerprint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A learned version space as stored on disk. Unlike [`ComputedBoundaries`], a model owns the
+/// dataset metadata its hypotheses refer to, so it can be read back without any other files.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Model {
+    pub format_version: u32,
+    pub metadata: DatasetMetadata,
+    pub fingerprint: DatasetFingerprint,
+    pub specific_boundary: Option<Vec<Attribute>>,
+    pub general_boundary: Vec<Vec<Attribute>>,
+
+    /// Whether the general boundary was narrowed to stay within a budget. Absent from models
+    /// written before budgets existed, which are all exact.
+    #[serde(default)]
+    pub is_approximate: bool,
+
+    /// Whether learning stopped before every training example of the dataset was processed, e.g.
+    /// because it was interrupted, so the model was only learned from the first
+    /// `fingerprint.n_examples` of them. Checkpoints are always partial. Absent from models written
+    /// before this was recorded.
+    #[serde(default)]
+    pub is_partial: bool,
+}
+
+impl Model {
+    pub fn new(
+        boundaries: &ComputedBoundaries,
+        metadata: &DatasetMetadata,
+        fingerprint: DatasetFingerprint,
+    ) -> Self {
+        Self {
+            format_version: MODEL_FORMAT_VERSION,
+            metadata: metadata.clone(),
+            fingerprint,
+            specific_boundary: boundaries
+                .specific_boundary
+                .as_ref()
+                .map(|hypothesis| hypothesis.attributes.clone()),
+            general_boundary: canonical_attributes(&boundaries.general_boundary),
+            is_approximate: boundaries.is_approximate,
+            is_partial: boundaries.interrupted_after.is_some()
+                || boundaries.budget_exceeded.is_some(),
+        }
+    }
+
+    /// Read a JSON model file, refusing models written in a different format version
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let model: Self = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+
+        if model.format_version != MODEL_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported model format version {} (expected {MODEL_FORMAT_VERSION})",
+                model.format_version
+            )
+            .into());
+        }
+
+        let n_columns = model.metadata.columns.len();
+        let hypotheses = model
+            .specific_boundary
+            .iter()
+            .chain(model.general_boundary.iter());
+        for attributes in hypotheses {
+            if attributes.len() != n_columns {
+                return Err(format!(
+                    "Model contains a hypothesis with {} attributes but its metadata describes {n_columns} columns",
+                    attributes.len()
+                )
+                .into());
+            }
+
+            for (index, (attribute, values)) in attributes
+                .iter()
+                .zip(model.metadata.columns.iter())
+                .enumerate()
+            {
+                if let Attribute::Value(value) = attribute {
+                    if usize::from(*value) >= values.len() {
+                        return Err(format!(
+                            "Model contains a hypothesis with value {value} for column {index}, but its metadata describes {} values for it",
+                            values.len()
+                        )
+                        .into());
+                    }
+                }
+            }
+        }
+
+        Ok(model)
+    }
+
+    /// Write the model as JSON
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(BufWriter::new(File::create(path)?), self)?;
+        Ok(())
+    }
+
+    /// Convert the model into boundaries that refer to the provided metadata, which must match
+    /// the metadata embedded in the model
+    pub fn into_boundaries(
+        self,
+        metadata: &Arc<DatasetMetadata>,
+    ) -> Result<ComputedBoundaries, Box<dyn Error>> {
+        if self.metadata != **metadata {
+            return Err("The model was learned using different dataset metadata".into());
+        }
+
+        let to_hypothesis = |attributes| Hypothesis {
+            attributes,
+            dataset_metadata: Arc::clone(metadata),
+        };
+
+        // Models written before boundaries were kept in canonical order may not be sorted
+        let mut boundaries = ComputedBoundaries {
+            specific_boundary: self.specific_boundary.map(to_hypothesis),
+            general_boundary: self
+                .general_boundary
+                .into_iter()
+                .map(to_hypothesis)
+                .collect(),
+            interrupted_after: None,
+            budget_exceeded: None,
+            is_approximate: self.is_approximate,
+        };
+        boundaries.canonicalize();
+
+        Ok(boundaries)
+    }
+
+    /// The boundaries stored in this model, referring to its embedded metadata
+    pub fn boundaries(&self) -> ComputedBoundaries {
+        let metadata = Arc::new(self.metadata.clone());
+        let to_hypothesis = |attributes: &Vec<Attribute>| Hypothesis {
+            attributes: attributes.clone(),
+            dataset_metadata: Arc::clone(&metadata),
+        };
+
+        let mut boundaries = ComputedBoundaries {
+            specific_boundary: self.specific_boundary.as_ref().map(to_hypothesis),
+            general_boundary: self.general_boundary.iter().map(to_hypothesis).collect(),
+            interrupted_after: None,
+            budget_exceeded: None,
+            is_approximate: self.is_approximate,
+        };
+        boundaries.canonicalize();
+
+        boundaries
+    }
+}
+
+/// The attributes of each hypothesis in a general boundary, in the canonical order of
+/// [`ComputedBoundaries::canonicalize`], so that model files are reproducible
+pub(crate) fn canonical_attributes(general_boundary: &[Hypothesis]) -> Vec<Vec<Attribute>> {
+    let mut attributes: Vec<Vec<Attribute>> = general_boundary
+        .iter()
+        .map(|hypothesis| hypothesis.attributes.clone())
+        .collect();
+    attributes.sort();
+    attributes.dedup();
+    attributes
+}
+
+impl ComputedBoundaries {
+    /// Save these boundaries as a model file, embedding the metadata and the fingerprint of the
+    /// training examples they were learned from
+    pub fn save<P: AsRef<Path>>(
+        &self,
+        path: P,
+        metadata: &DatasetMetadata,
+        fingerprint: DatasetFingerprint,
+    ) -> Result<(), Box<dyn Error>> {
+        Model::new(self, metadata, fingerprint).save(path)
+    }
+
+    /// Load boundaries from a model file. The metadata embedded in the model must match the
+    /// provided metadata, which the loaded hypotheses then refer to.
+    pub fn load<P: AsRef<Path>>(
+        path: P,
+        metadata: &Arc<DatasetMetadata>,
+    ) -> Result<Self, Box<dyn Error>> {
+        Model::load(path)?.into_boundaries(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::Solver;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("ccelm-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn test_model_round_trip() {
+        let mut fingerprint = DatasetFingerprint::new();
+        for example in enjoysport_examples().iter() {
+            fingerprint.update(example);
+        }
+
+        let boundaries = Solver::new(enjoysport_examples(), &DATASET_METADATA).solve();
+
+        let path = temp_path("round-trip.json");
+        boundaries
+            .save(&path, &DATASET_METADATA, fingerprint)
+            .unwrap();
+
+        let model = Model::load(&path).unwrap();
+        assert_eq!(model.fingerprint, fingerprint);
+        assert_eq!(model.metadata, **DATASET_METADATA);
+
+        let loaded = ComputedBoundaries::load(&path, &DATASET_METADATA).unwrap();
+        assert_eq!(loaded.specific_boundary, boundaries.specific_boundary);
+        assert_eq!(loaded.general_boundary, boundaries.general_boundary);
+        assert_eq!(
+            model.boundaries().general_boundary,
+            boundaries.general_boundary
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_model_general_boundary_is_canonical() {
+        let mut boundaries = Solver::new(enjoysport_examples(), &DATASET_METADATA).solve();
+        assert!(boundaries.is_canonical());
+        boundaries.general_boundary.reverse();
+
+        let model = Model::new(&boundaries, &DATASET_METADATA, DatasetFingerprint::new());
+        let mut sorted = model.general_boundary.clone();
+        sorted.sort();
+        assert_eq!(model.general_boundary, sorted);
+
+        // Boundaries read back from an unsorted model, e.g. one written by an older version, are
+        // put in canonical order as well
+        let mut unsorted = model.clone();
+        unsorted.general_boundary.reverse();
+        assert!(unsorted.boundaries().is_canonical());
+        assert!(unsorted
+            .into_boundaries(&DATASET_METADATA)
+            .unwrap()
+            .is_canonical());
+    }
+
+    #[test]
+    fn test_partial_boundaries_are_saved_as_partial() {
+        let mut boundaries = Solver::new(enjoysport_examples(), &DATASET_METADATA).solve();
+        let model = Model::new(&boundaries, &DATASET_METADATA, DatasetFingerprint::new());
+        assert!(!model.is_partial);
+
+        boundaries.interrupted_after = Some(2);
+        let path = temp_path("partial.json");
+        boundaries
+            .save(&path, &DATASET_METADATA, DatasetFingerprint::new())
+            .unwrap();
+        assert!(Model::load(&path).unwrap().is_partial);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_load_rejects_different_metadata() {
+        let boundaries = Solver::new(enjoysport_examples(), &DATASET_METADATA).solve();
+
+        let path = temp_path("different-metadata.json");
+        boundaries
+            .save(&path, &DATASET_METADATA, DatasetFingerprint::new())
+            .unwrap();
+
+        let mut metadata = DatasetMetadata::clone(&DATASET_METADATA);
+        metadata.columns[0].push("Snowy".to_string());
+        assert!(ComputedBoundaries::load(&path, &Arc::new(metadata)).is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_load_rejects_values_outside_metadata() {
+        let boundaries = Solver::new(enjoysport_examples(), &DATASET_METADATA).solve();
+        let mut model = Model::new(&boundaries, &DATASET_METADATA, DatasetFingerprint::new());
+        model.general_boundary[0][0] = Attribute::Value(3);
+
+        let path = temp_path("value-outside-metadata.json");
+        model.save(&path).unwrap();
+
+        assert!(Model::load(&path).is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_fingerprint_depends_on_order() {
+        let examples = enjoysport_examples();
+
+        let mut forward = DatasetFingerprint::new();
+        examples.iter().for_each(|example| forward.update(example));
+
+        let mut backward = DatasetFingerprint::new();
+        examples
+            .iter()
+            .rev()
+            .for_each(|example| backward.update(example));
+
+        assert_eq!(forward.n_examples, backward.n_examples);
+        assert_ne!(forward, backward);
+    }
+}
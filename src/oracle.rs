@@ -0,0 +1,213 @@
+use std::error::Error;
+use std::ffi::OsString;
+use std::io::{BufRead, ErrorKind, Write};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+use crate::reader::DatasetMetadata;
+use crate::Query;
+
+/// Provides labels for the instances proposed by a [`crate::QueryGenerator`]
+pub trait Oracle {
+    /// Label the queried instance. Returns `None` if the oracle declines to answer any further
+    /// queries.
+    fn label(&mut self, query: &Query) -> Result<Option<bool>, Box<dyn Error>>;
+}
+
+/// Asks a human to label each instance at the terminal
+pub struct TerminalOracle<'a, R, W> {
+    input: R,
+    output: W,
+    headers: Vec<String>,
+    metadata: &'a DatasetMetadata,
+}
+
+impl<'a, R: BufRead, W: Write> TerminalOracle<'a, R, W> {
+    /// `headers` names each attribute column when presenting an instance
+    pub fn new(input: R, output: W, headers: Vec<String>, metadata: &'a DatasetMetadata) -> Self {
+        Self {
+            input,
+            output,
+            headers,
+            metadata,
+        }
+    }
+}
+
+impl<R: BufRead, W: Write> Oracle for TerminalOracle<'_, R, W> {
+    fn label(&mut self, query: &Query) -> Result<Option<bool>, Box<dyn Error>> {
+        writeln!(self.output)?;
+        for (header, value) in self
+            .headers
+            .iter()
+            .zip(query.to_valued_strings(self.metadata))
+        {
+            writeln!(self.output, "  {header}: {value}")?;
+        }
+
+        loop {
+            write!(self.output, "Is this a positive example? [y/n/q] ")?;
+            self.output.flush()?;
+
+            let mut answer = String::new();
+            if self.input.read_line(&mut answer)? == 0 {
+                return Ok(None);
+            }
+
+            match answer.trim().to_lowercase().as_str() {
+                "y" | "yes" | "true" => return Ok(Some(true)),
+                "n" | "no" | "false" => return Ok(Some(false)),
+                "q" | "quit" => return Ok(None),
+                _ => writeln!(self.output, "Please answer y(es), n(o) or q(uit)")?,
+            }
+        }
+    }
+}
+
+/// Labels each instance by running an external program. The instance is written to the program's
+/// stdin as a single CSV line in the dataset's vocabulary, comma-separated whatever the dataset's
+/// own delimiter, and the program is expected to print `true` or `false` to stdout.
+pub struct CommandOracle<'a> {
+    program: OsString,
+    args: Vec<OsString>,
+    metadata: &'a DatasetMetadata,
+}
+
+impl<'a> CommandOracle<'a> {
+    pub fn new<P, I, A>(program: P, args: I, metadata: &'a DatasetMetadata) -> Self
+    where
+        P: Into<OsString>,
+        I: IntoIterator<Item = A>,
+        A: Into<OsString>,
+    {
+        Self {
+            program: program.into(),
+            args: args.into_iter().map(Into::into).collect(),
+            metadata,
+        }
+    }
+}
+
+impl Oracle for CommandOracle<'_> {
+    fn label(&mut self, query: &Query) -> Result<Option<bool>, Box<dyn Error>> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.write_record(
+            query
+                .attributes
+                .iter()
+                .enumerate()
+                .map(|(index, attribute)| attribute.to_dataset_string(index, self.metadata)),
+        )?;
+        let line = String::from_utf8(writer.into_inner()?)?;
+        let line = line.trim_end_matches(['\r', '\n']);
+
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        // Dropping stdin once written closes it, signalling the end of the instance. Oracles that
+        // answer without reading the instance may already have exited, which is fine
+        match writeln!(child.stdin.take().unwrap(), "{line}") {
+            Err(err) if err.kind() != ErrorKind::BrokenPipe => return Err(err.into()),
+            _ => {}
+        }
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(format!("Oracle exited with {} for instance {line}", output.status).into());
+        }
+
+        let answer = String::from_utf8(output.stdout)?;
+        let is_positive = bool::from_str(answer.trim()).map_err(|_| {
+            format!("Oracle answered {answer:?} for instance {line}, expected true or false")
+        })?;
+
+        Ok(Some(is_positive))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::test_fixtures::DATASET_METADATA;
+    use crate::Attribute;
+
+    #[test]
+    fn test_terminal_oracle_reprompts_until_answered() {
+        let query = Query {
+            attributes: vec![Attribute::Value(0); 6],
+            positive_fraction: 0.5,
+        };
+        let headers = (0..6).map(|index| format!("column_{index}")).collect();
+
+        let mut output = vec![];
+        let mut oracle = TerminalOracle::new(
+            Cursor::new("maybe\nYes\nn\n"),
+            &mut output,
+            headers,
+            &DATASET_METADATA,
+        );
+
+        assert_eq!(oracle.label(&query).unwrap(), Some(true));
+        assert_eq!(oracle.label(&query).unwrap(), Some(false));
+        assert_eq!(oracle.label(&query).unwrap(), None);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("column_0: Sunny"));
+        assert!(output.contains("Please answer"));
+    }
+
+    #[test]
+    fn test_command_oracle() {
+        let mut oracle = CommandOracle::new(
+            "sh",
+            ["-c", "grep -q '^Sunny,' && echo true || echo false"],
+            &DATASET_METADATA,
+        );
+
+        let mut query = Query {
+            attributes: vec![Attribute::Value(0); 6],
+            positive_fraction: 0.5,
+        };
+        assert_eq!(oracle.label(&query).unwrap(), Some(true));
+
+        query.attributes[0] = Attribute::Value(1);
+        assert_eq!(oracle.label(&query).unwrap(), Some(false));
+    }
+
+    #[test]
+    fn test_command_oracle_writes_csv_for_tab_delimited_datasets() {
+        let mut metadata = DatasetMetadata::clone(&DATASET_METADATA);
+        metadata.delimiter = '\t';
+        metadata.columns[0][0] = "Sunny, bright".to_string();
+        let mut oracle = CommandOracle::new(
+            "sh",
+            [
+                "-c",
+                "grep -q '^\"Sunny, bright\",Warm,' && echo true || echo false",
+            ],
+            &metadata,
+        );
+
+        let query = Query {
+            attributes: vec![Attribute::Value(0); 6],
+            positive_fraction: 0.5,
+        };
+        assert_eq!(oracle.label(&query).unwrap(), Some(true));
+    }
+
+    #[test]
+    fn test_command_oracle_rejects_invalid_answer() {
+        let mut oracle = CommandOracle::new("sh", ["-c", "echo maybe"], &DATASET_METADATA);
+        let query = Query {
+            attributes: vec![Attribute::Value(0); 6],
+            positive_fraction: 0.5,
+        };
+
+        assert!(oracle.label(&query).is_err());
+    }
+}
@@ -0,0 +1,480 @@
+use std::error::Error;
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::reader::DatasetMetadata;
+use crate::{
+    Attribute, ComputedBoundaries, Hypothesis, PackedHypothesis, Packing, TrainingExample,
+};
+
+/// An unlabelled instance proposed by a [`QueryGenerator`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    /// The attribute values of the instance. Every attribute is a concrete [`Attribute::Value`]
+    /// unless the query was taken from a pool containing wildcards.
+    pub attributes: Vec<Attribute>,
+
+    /// Estimated fraction of the version space that would classify this instance as positive
+    pub positive_fraction: f64,
+}
+
+impl Query {
+    /// Attach a label to this query so that it can be fed to a solver
+    pub fn into_training_example(self, is_positive: bool) -> TrainingExample {
+        TrainingExample {
+            attributes: self.attributes,
+            is_positive,
+        }
+    }
+
+    /// Render each attribute using the value names from the dataset metadata
+    pub fn to_valued_strings(&self, dataset_metadata: &DatasetMetadata) -> Vec<String> {
+        self.attributes
+            .iter()
+            .enumerate()
+            .map(|(index, attribute)| attribute.to_valued_string(index, dataset_metadata))
+            .collect()
+    }
+}
+
+/// Proposes the unlabelled instances that are most informative to label next.
+///
+/// An instance is only informative if the version space disagrees on it, which is the case
+/// exactly when the specific boundary rejects it and at least one member of the general boundary
+/// accepts it. Among the informative instances, the generator prefers the one whose label splits
+/// the version space as evenly as possible, so that either answer eliminates roughly half of the
+/// remaining hypotheses. How evenly an instance splits the version space is estimated against a
+/// set of hypotheses drawn uniformly using a [`crate::HypothesisSampler`].
+#[derive(Debug)]
+pub struct QueryGenerator {
+    specific_boundary: Hypothesis,
+    general_boundary: Vec<Hypothesis>,
+    samples: Vec<Hypothesis>,
+    packed: Option<PackedVersionSpace>,
+    dataset_metadata: Arc<DatasetMetadata>,
+    rng: StdRng,
+}
+
+/// The boundaries and samples of a [`QueryGenerator`] packed for faster classification, if the
+/// dataset allows it
+#[derive(Debug)]
+struct PackedVersionSpace {
+    packing: Packing,
+    specific_boundary: PackedHypothesis,
+    general_boundary: Vec<PackedHypothesis>,
+    samples: Vec<PackedHypothesis>,
+}
+
+/// The value of an attribute at `index` in its column. [`QueryGenerator::new`] refuses columns with
+/// more values than a `u8` can index, so this can't fail.
+fn value(index: usize) -> Attribute {
+    Attribute::Value(u8::try_from(index).expect("Columns hold at most 256 values"))
+}
+
+/// How many instances the hill climb in [`QueryGenerator::suggest`] is started from
+const RESTARTS: usize = 8;
+
+/// Upper bound on the number of improving passes over the attributes per restart
+const MAX_PASSES: usize = 4;
+
+impl QueryGenerator {
+    /// Create a new generator that estimates splits using `n_samples` hypotheses. Returns `None`
+    /// if the boundaries do not enclose any hypotheses, and fails if an attribute takes no values or
+    /// more values than an [`Attribute::Value`] can tell apart.
+    pub fn new(
+        boundaries: &ComputedBoundaries,
+        n_samples: usize,
+        seed: u64,
+    ) -> Result<Option<Self>, Box<dyn Error>> {
+        let Some(specific_boundary) = boundaries.specific_boundary.clone() else {
+            return Ok(None);
+        };
+
+        if let Some(index) = specific_boundary
+            .dataset_metadata
+            .columns
+            .iter()
+            .position(|values| values.is_empty())
+        {
+            return Err(format!(
+                "Column {index} has no values, so no instances can be generated for queries"
+            )
+            .into());
+        }
+
+        let max_values = usize::from(u8::MAX) + 1;
+        if let Some(index) = specific_boundary
+            .dataset_metadata
+            .columns
+            .iter()
+            .position(|values| values.len() > max_values)
+        {
+            return Err(format!(
+                "Column {index} has more than {max_values} values, which queries can't be generated for"
+            )
+            .into());
+        }
+
+        let Some(sampler) = boundaries.sampler(seed) else {
+            return Ok(None);
+        };
+        let samples: Vec<Hypothesis> = sampler.take(n_samples).collect();
+
+        let packed = Packing::new(&specific_boundary.dataset_metadata).map(|packing| {
+            let pack_all = |hypotheses: &[Hypothesis]| {
+                hypotheses
+                    .iter()
+                    .map(|hypothesis| packing.pack(&hypothesis.attributes))
+                    .collect()
+            };
+            PackedVersionSpace {
+                specific_boundary: packing.pack(&specific_boundary.attributes),
+                general_boundary: pack_all(&boundaries.general_boundary),
+                samples: pack_all(&samples),
+                packing,
+            }
+        });
+
+        Ok(Some(Self {
+            dataset_metadata: Arc::clone(&specific_boundary.dataset_metadata),
+            specific_boundary,
+            general_boundary: boundaries.general_boundary.clone(),
+            samples,
+            packed,
+            // Offset the seed so that the search doesn't replay the sampler's random stream
+            rng: StdRng::seed_from_u64(seed.wrapping_add(1)),
+        }))
+    }
+
+    /// Whether the version space disagrees on the classification of this instance
+    pub fn is_informative(&self, attributes: &[Attribute]) -> bool {
+        if let Some(packed) = &self.packed {
+            let instance = packed.packing.pack(attributes);
+            return !packed.specific_boundary.covers(&instance)
+                && packed
+                    .general_boundary
+                    .iter()
+                    .any(|hypothesis| hypothesis.covers(&instance));
+        }
+
+        let instance = TrainingExample::new(attributes, true);
+
+        !self.specific_boundary.classify(&instance)
+            && self
+                .general_boundary
+                .iter()
+                .any(|hypothesis| hypothesis.classify(&instance))
+    }
+
+    /// Estimate the fraction of the version space that classifies this instance as positive
+    pub fn positive_fraction(&self, attributes: &[Attribute]) -> f64 {
+        let positives = match &self.packed {
+            Some(packed) => {
+                let instance = packed.packing.pack(attributes);
+                packed
+                    .samples
+                    .iter()
+                    .filter(|hypothesis| hypothesis.covers(&instance))
+                    .count()
+            }
+            None => {
+                let instance = TrainingExample::new(attributes, true);
+                self.samples
+                    .iter()
+                    .filter(|hypothesis| hypothesis.classify(&instance))
+                    .count()
+            }
+        };
+
+        positives as f64 / self.samples.len().max(1) as f64
+    }
+
+    /// Pick the most informative instance from a pool of candidates. Returns `None` if none of
+    /// the candidates are informative.
+    pub fn suggest_from_pool<I>(&self, pool: I) -> Option<Query>
+    where
+        I: IntoIterator<Item = Vec<Attribute>>,
+    {
+        pool.into_iter()
+            .filter(|attributes| self.is_informative(attributes))
+            .map(|attributes| Query {
+                positive_fraction: self.positive_fraction(&attributes),
+                attributes,
+            })
+            .min_by(|left, right| {
+                imbalance(left.positive_fraction).total_cmp(&imbalance(right.positive_fraction))
+            })
+    }
+
+    /// Search the instance space described by the dataset metadata for the instance that splits
+    /// the version space most evenly. Returns `None` once no informative instance remains, i.e.
+    /// the version space has converged.
+    ///
+    /// The search hill climbs from several starting instances, repeatedly changing whichever
+    /// single attribute value most improves the split. Half of the starting points are built from
+    /// members of the general boundary so that the climb begins from an informative instance.
+    pub fn suggest(&mut self) -> Option<Query> {
+        let mut best: Option<Query> = None;
+
+        for restart in 0..RESTARTS {
+            let start = if restart % 2 == 0 {
+                self.informative_instance()
+            } else {
+                None
+            }
+            .unwrap_or_else(|| self.random_instance());
+
+            let attributes = self.hill_climb(start);
+            if !self.is_informative(&attributes) {
+                continue;
+            }
+
+            let query = Query {
+                positive_fraction: self.positive_fraction(&attributes),
+                attributes,
+            };
+
+            let is_better = best.as_ref().is_none_or(|best| {
+                imbalance(query.positive_fraction) < imbalance(best.positive_fraction)
+            });
+            if is_better {
+                best = Some(query);
+            }
+        }
+
+        best
+    }
+
+    fn random_instance(&mut self) -> Vec<Attribute> {
+        self.dataset_metadata
+            .columns
+            .iter()
+            .map(|values| value(self.rng.gen_range(0..values.len())))
+            .collect()
+    }
+
+    /// Build an instance that is accepted by a random member of the general boundary but rejected
+    /// by the specific boundary
+    fn informative_instance(&mut self) -> Option<Vec<Attribute>> {
+        let general = self.general_boundary.choose(&mut self.rng)?.clone();
+
+        let mut attributes: Vec<Attribute> = general
+            .attributes
+            .iter()
+            .zip(self.specific_boundary.attributes.iter())
+            .zip(self.dataset_metadata.columns.iter())
+            .map(|((upper, lower), values)| match (upper, lower) {
+                (Attribute::Value(value), _) | (Attribute::Any, Attribute::Value(value)) => {
+                    Attribute::Value(*value)
+                }
+                _ => value(self.rng.gen_range(0..values.len())),
+            })
+            .collect();
+
+        // Move a single attribute out of the specific boundary whilst staying inside the general
+        // hypothesis
+        let mut loosened: Vec<usize> = general
+            .attributes
+            .iter()
+            .zip(self.specific_boundary.attributes.iter())
+            .enumerate()
+            .filter(|(index, (upper, lower))| {
+                **upper == Attribute::Any
+                    && matches!(lower, Attribute::Value(_))
+                    && self.dataset_metadata.columns[*index].len() > 1
+            })
+            .map(|(index, _)| index)
+            .collect();
+        loosened.shuffle(&mut self.rng);
+
+        if let Some(index) = loosened.first() {
+            let Attribute::Value(value) = attributes[*index] else {
+                unreachable!()
+            };
+            let n_values = self.dataset_metadata.columns[*index].len();
+            let offset = self.rng.gen_range(1..n_values);
+            attributes[*index] = self::value((usize::from(value) + offset) % n_values);
+        }
+
+        self.is_informative(&attributes).then_some(attributes)
+    }
+
+    /// Greedily improve the split of an instance one attribute at a time.
+    ///
+    /// A sampled hypothesis accepts an instance exactly when none of its attributes reject the
+    /// corresponding value, so tracking the number of rejecting attributes per sample allows each
+    /// candidate change to be scored without re-classifying the whole instance.
+    fn hill_climb(&self, mut attributes: Vec<Attribute>) -> Vec<Attribute> {
+        let rejects = |hypothesis: &Hypothesis, index: usize, value: &Attribute| {
+            !hypothesis.attributes[index].is_consistent(value)
+        };
+
+        let mut rejections: Vec<usize> = self
+            .samples
+            .iter()
+            .map(|hypothesis| {
+                attributes
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, value)| rejects(hypothesis, *index, value))
+                    .count()
+            })
+            .collect();
+
+        let n_samples = self.samples.len() as i64;
+        let score = |positives: i64| (2 * positives - n_samples).abs();
+        let mut current = score(rejections.iter().filter(|count| **count == 0).count() as i64);
+
+        for _ in 0..MAX_PASSES {
+            let mut improved = false;
+
+            for (index, values) in self.dataset_metadata.columns.iter().enumerate() {
+                let mut best: Option<(i64, Attribute)> = None;
+
+                for value in (0..values.len()).map(value) {
+                    if value == attributes[index] {
+                        continue;
+                    }
+
+                    let positives = self
+                        .samples
+                        .iter()
+                        .zip(rejections.iter())
+                        .filter(|(hypothesis, count)| {
+                            let count = **count
+                                - usize::from(rejects(hypothesis, index, &attributes[index]))
+                                + usize::from(rejects(hypothesis, index, &value));
+                            count == 0
+                        })
+                        .count() as i64;
+
+                    let candidate = score(positives);
+                    if candidate < best.as_ref().map_or(current, |(best, _)| *best) {
+                        best = Some((candidate, value));
+                    }
+                }
+
+                if let Some((candidate, value)) = best {
+                    for (hypothesis, count) in self.samples.iter().zip(rejections.iter_mut()) {
+                        *count = *count
+                            - usize::from(rejects(hypothesis, index, &attributes[index]))
+                            + usize::from(rejects(hypothesis, index, &value));
+                    }
+                    attributes[index] = value;
+                    current = candidate;
+                    improved = true;
+                }
+            }
+
+            if !improved {
+                break;
+            }
+        }
+
+        attributes
+    }
+}
+
+/// Distance of a positive fraction from an even split
+fn imbalance(positive_fraction: f64) -> f64 {
+    (positive_fraction - 0.5).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::Solver;
+
+    fn enjoysport_boundaries() -> ComputedBoundaries {
+        Solver::new(enjoysport_examples(), &DATASET_METADATA).solve()
+    }
+
+    #[test]
+    fn test_suggestion_is_informative() {
+        let boundaries = enjoysport_boundaries();
+        let mut generator = QueryGenerator::new(&boundaries, 256, 42).unwrap().unwrap();
+
+        let query = generator.suggest().unwrap();
+
+        assert!(generator.is_informative(&query.attributes));
+        assert!(query.positive_fraction > 0.0 && query.positive_fraction < 1.0);
+    }
+
+    #[test]
+    fn test_suggest_from_pool_skips_uninformative() {
+        let boundaries = enjoysport_boundaries();
+        let generator = QueryGenerator::new(&boundaries, 256, 42).unwrap().unwrap();
+
+        let parse = |record: &str| {
+            TrainingExample::from_str(record, &DATASET_METADATA)
+                .unwrap()
+                .attributes
+        };
+
+        // Every hypothesis in the version space accepts the first and rejects the second
+        let pool = vec![
+            parse("Sunny,Warm,Normal,Strong,Cool,Change,true"),
+            parse("Rainy,Cold,Normal,Weak,Warm,Same,true"),
+            parse("Sunny,Warm,Normal,Weak,Warm,Same,true"),
+        ];
+
+        let query = generator.suggest_from_pool(pool.clone()).unwrap();
+        assert_eq!(query.attributes, pool[2]);
+
+        assert!(generator.suggest_from_pool(pool[..2].to_vec()).is_none());
+    }
+
+    #[test]
+    fn test_no_suggestion_once_converged() {
+        let hypothesis =
+            Hypothesis::from_str("Sunny,Warm,?,Strong,?,?", &DATASET_METADATA).unwrap();
+        let boundaries = ComputedBoundaries {
+            specific_boundary: Some(hypothesis.clone()),
+            general_boundary: vec![hypothesis],
+            interrupted_after: None,
+            budget_exceeded: None,
+            is_approximate: false,
+        };
+
+        let mut generator = QueryGenerator::new(&boundaries, 64, 0).unwrap().unwrap();
+        assert!(generator.suggest().is_none());
+    }
+
+    #[test]
+    fn test_rejects_attributes_with_too_many_values() {
+        let metadata = Arc::new(DatasetMetadata::new(
+            vec![(0..300).map(|value| format!("v{value}")).collect()],
+            None,
+            None,
+            ',',
+        ));
+        let boundaries = ComputedBoundaries {
+            specific_boundary: Some(Hypothesis::specific(1, &metadata)),
+            general_boundary: vec![Hypothesis::general(1, &metadata)],
+            ..Default::default()
+        };
+
+        assert!(QueryGenerator::new(&boundaries, 64, 0).is_err());
+    }
+
+    #[test]
+    fn test_rejects_attributes_without_values() {
+        let metadata = Arc::new(DatasetMetadata::new(
+            vec![vec!["a".to_string(), "b".to_string()], vec![]],
+            None,
+            None,
+            ',',
+        ));
+        let boundaries = ComputedBoundaries {
+            specific_boundary: Some(Hypothesis::specific(2, &metadata)),
+            general_boundary: vec![Hypothesis::general(2, &metadata)],
+            ..Default::default()
+        };
+
+        assert!(QueryGenerator::new(&boundaries, 64, 0).is_err());
+    }
+}
@@ -1,30 +1,59 @@
 use std::error::Error;
 use std::fs::File;
+use std::io::Read;
 use std::path::Path;
 use std::str::FromStr;
 
 use csv::StringRecord;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{Attribute, TrainingExample};
 
 pub struct DatasetReader {
-    reader: csv::Reader<File>,
+    reader: csv::Reader<Box<dyn Read + Send>>,
     metadata: DatasetMetadata,
 }
 
 impl DatasetReader {
+    /// Open the dataset at the given path, or read it from stdin if the path is `-`
     pub fn new<P: AsRef<Path>>(
         dataset_path: P,
         metadata: DatasetMetadata,
     ) -> Result<Self, csv::Error> {
-        Ok(Self {
+        let dataset_path = dataset_path.as_ref();
+        let reader: Box<dyn Read + Send> = if dataset_path == Path::new("-") {
+            Box::new(std::io::stdin())
+        } else {
+            Box::new(File::open(dataset_path)?)
+        };
+
+        Ok(Self::from_reader(reader, metadata))
+    }
+
+    /// Read the dataset from any source, such as a pipe. Rows are only read as they are consumed
+    pub fn from_reader<R: Read + Send + 'static>(reader: R, metadata: DatasetMetadata) -> Self {
+        let reader: Box<dyn Read + Send> = Box::new(reader);
+        Self {
+            // The header isn't used to parse the rows, and some datasets such as the Boundaries
+            // ones have a malformed one, so the rows may have a different number of columns. Each
+            // row is checked against the metadata instead.
             reader: csv::ReaderBuilder::new()
                 .trim(csv::Trim::All)
+                .flexible(true)
                 .delimiter(metadata.delimiter.try_into().unwrap())
-                .from_path(dataset_path)?,
+                .from_reader(reader),
             metadata,
-        })
+        }
+    }
+
+    /// All column headers, including that of the label column
+    pub fn headers(&mut self) -> Result<Vec<String>, csv::Error> {
+        Ok(self
+            .reader
+            .headers()?
+            .into_iter()
+            .map(|header| header.to_string())
+            .collect())
     }
 
     pub fn attributes(&mut self) -> Result<Vec<&str>, csv::Error> {
@@ -45,10 +74,19 @@ impl Iterator for DatasetReader {
     fn next(&mut self) -> Option<Self::Item> {
         let mut record = StringRecord::new();
 
-        let did_read = self.reader.read_record(&mut record);
+        match self.reader.read_record(&mut record) {
+            Ok(false) => return None,
+            Err(err) => return Some(Err(err.into())),
+            Ok(true) => {}
+        }
 
-        if let Ok(false) = did_read {
-            return None;
+        let n_columns = self.metadata.columns.len();
+        if record.len() != n_columns + 1 {
+            return Some(Err(format!(
+                "Expected {n_columns} attributes and a label but found {} columns",
+                record.len()
+            )
+            .into()));
         }
 
         let maybe_is_positive = bool::from_str(record.get(record.len() - 1).unwrap());
@@ -68,7 +106,59 @@ impl Iterator for DatasetReader {
     }
 }
 
-#[derive(Deserialize, Clone, Debug)]
+/// Reads unlabelled instances, such as a pool of candidate queries. Rows may optionally carry a
+/// trailing label column, in which case it is ignored.
+pub struct InstanceReader {
+    reader: csv::Reader<File>,
+    metadata: DatasetMetadata,
+}
+
+impl InstanceReader {
+    pub fn new<P: AsRef<Path>>(
+        dataset_path: P,
+        metadata: DatasetMetadata,
+    ) -> Result<Self, csv::Error> {
+        Ok(Self {
+            reader: csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .delimiter(metadata.delimiter.try_into().unwrap())
+                .from_path(dataset_path)?,
+            metadata,
+        })
+    }
+}
+
+impl Iterator for InstanceReader {
+    type Item = Result<Vec<Attribute>, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = StringRecord::new();
+
+        match self.reader.read_record(&mut record) {
+            Ok(false) => return None,
+            Err(err) => return Some(Err(err.into())),
+            Ok(true) => {}
+        }
+
+        let n_columns = self.metadata.columns.len();
+        if record.len() != n_columns && record.len() != n_columns + 1 {
+            return Some(Err(format!(
+                "Expected {n_columns} attributes but found {} columns",
+                record.len()
+            )
+            .into()));
+        }
+
+        Some(Ok(record
+            .iter()
+            .take(n_columns)
+            .enumerate()
+            .map(|(index, record)| Attribute::new(record, index, &self.metadata))
+            .collect()))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct DatasetMetadata {
     pub columns: Vec<Vec<String>>,
 
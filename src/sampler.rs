@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::reader::DatasetMetadata;
+use crate::{Attribute, ComputedBoundaries, Hypothesis, PackedHypothesis, Packing};
+
+/// Draws hypotheses uniformly at random from the version space bounded by a set of
+/// [`ComputedBoundaries`].
+///
+/// The version space is the union of the intervals $[s, g]$ for each $g$ in the general boundary.
+/// Each interval is a product of per-attribute choices, so it can be sampled from directly. To
+/// avoid favouring hypotheses that lie in several overlapping intervals, an interval is first
+/// picked with probability proportional to its size and the drawn hypothesis is then only accepted
+/// with probability $1/c$, where $c$ is the number of intervals containing it. The accepted
+/// hypotheses are therefore uniformly distributed over the version space itself rather than over
+/// the members of the general boundary.
+#[derive(Debug)]
+pub struct HypothesisSampler {
+    general_boundary: Vec<Hypothesis>,
+    packed_general_boundary: Option<(Packing, Vec<PackedHypothesis>)>,
+    intervals: Vec<Vec<Vec<Attribute>>>,
+    weights: WeightedIndex<f64>,
+    dataset_metadata: Arc<DatasetMetadata>,
+    rng: StdRng,
+}
+
+impl HypothesisSampler {
+    /// Create a new sampler seeded with `seed`. Returns `None` if the boundaries do not enclose
+    /// any hypotheses (e.g. the specific boundary is missing or the version space has collapsed).
+    pub fn new(boundaries: &ComputedBoundaries, seed: u64) -> Option<Self> {
+        let specific_boundary = boundaries.specific_boundary.as_ref()?;
+        let dataset_metadata = Arc::clone(&specific_boundary.dataset_metadata);
+
+        let intervals: Vec<Vec<Vec<Attribute>>> = boundaries
+            .general_boundary
+            .iter()
+            .map(|general| {
+                general
+                    .attributes
+                    .iter()
+                    .zip(specific_boundary.attributes.iter())
+                    .enumerate()
+                    .map(|(index, (upper, lower))| {
+                        Self::attribute_interval(upper, lower, &dataset_metadata.columns[index])
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Interval sizes can be astronomically large for wide datasets, f64 is plenty precise for
+        // a sampling weight
+        let sizes = intervals.iter().map(|interval| {
+            interval
+                .iter()
+                .map(|candidates| candidates.len() as f64)
+                .product::<f64>()
+        });
+
+        let weights = WeightedIndex::new(sizes).ok()?;
+
+        let packed_general_boundary = Packing::new(&dataset_metadata).map(|packing| {
+            let packed = boundaries
+                .general_boundary
+                .iter()
+                .map(|general| packing.pack(&general.attributes))
+                .collect();
+            (packing, packed)
+        });
+
+        Some(Self {
+            general_boundary: boundaries.general_boundary.clone(),
+            packed_general_boundary,
+            intervals,
+            weights,
+            dataset_metadata,
+            rng: StdRng::seed_from_u64(seed),
+        })
+    }
+
+    /// Every attribute that is at least as general as `lower` and at most as general as `upper`
+    fn attribute_interval(
+        upper: &Attribute,
+        lower: &Attribute,
+        possible_values: &[String],
+    ) -> Vec<Attribute> {
+        std::iter::once(Attribute::NoValue)
+            .chain(
+                (0..possible_values.len())
+                    .map(|index| Attribute::Value(u8::try_from(index).unwrap())),
+            )
+            .chain(std::iter::once(Attribute::Any))
+            .filter(|candidate| upper.is_consistent(candidate) && candidate.is_consistent(lower))
+            .collect()
+    }
+
+    /// Draw a single hypothesis from the version space
+    pub fn sample(&mut self) -> Hypothesis {
+        loop {
+            let interval = &self.intervals[self.weights.sample(&mut self.rng)];
+
+            let attributes: Vec<Attribute> = interval
+                .iter()
+                .map(|candidates| candidates[self.rng.gen_range(0..candidates.len())].clone())
+                .collect();
+
+            let hypothesis = Hypothesis {
+                attributes,
+                dataset_metadata: Arc::clone(&self.dataset_metadata),
+            };
+
+            let containing_intervals = match &self.packed_general_boundary {
+                Some((packing, general_boundary)) => {
+                    let packed = packing.pack(&hypothesis.attributes);
+                    general_boundary
+                        .iter()
+                        .filter(|general| general.is_more_general(&packed))
+                        .count()
+                }
+                None => self
+                    .general_boundary
+                    .iter()
+                    .filter(|general| general.is_more_general(&hypothesis))
+                    .count(),
+            };
+
+            if self.rng.gen_range(0..containing_intervals) == 0 {
+                return hypothesis;
+            }
+        }
+    }
+}
+
+impl Iterator for HypothesisSampler {
+    type Item = Hypothesis;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.sample())
+    }
+}
+
+impl ComputedBoundaries {
+    /// Create a seeded [`HypothesisSampler`] over the version space enclosed by these boundaries
+    pub fn sampler(&self, seed: u64) -> Option<HypothesisSampler> {
+        HypothesisSampler::new(self, seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::Solver;
+
+    fn enjoysport_boundaries() -> ComputedBoundaries {
+        Solver::new(enjoysport_examples(), &DATASET_METADATA).solve()
+    }
+
+    #[test]
+    fn test_sampler_is_reproducible() {
+        let boundaries = enjoysport_boundaries();
+
+        let first: Vec<_> = boundaries.sampler(42).unwrap().take(50).collect();
+        let second: Vec<_> = boundaries.sampler(42).unwrap().take(50).collect();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_sampler_is_uniform_over_version_space() {
+        let boundaries = enjoysport_boundaries();
+
+        // The book example has exactly six hypotheses in its version space
+        let version_space = [
+            "Sunny,Warm,?,Strong,?,?",
+            "Sunny,?,?,Strong,?,?",
+            "Sunny,Warm,?,?,?,?",
+            "?,Warm,?,Strong,?,?",
+            "Sunny,?,?,?,?,?",
+            "?,Warm,?,?,?,?",
+        ]
+        .map(|record| Hypothesis::from_str(record, &DATASET_METADATA).unwrap());
+
+        let mut counts: HashMap<Hypothesis, usize> = HashMap::new();
+        for hypothesis in boundaries.sampler(7).unwrap().take(6000) {
+            *counts.entry(hypothesis).or_default() += 1;
+        }
+
+        assert_eq!(counts.len(), version_space.len());
+        for hypothesis in version_space.iter() {
+            let count = counts[hypothesis];
+            assert!(
+                (800..1200).contains(&count),
+                "{hypothesis} drawn {count} times"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sampler_requires_specific_boundary() {
+        assert!(ComputedBoundaries::default().sampler(0).is_none());
+    }
+}
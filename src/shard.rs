@@ -0,0 +1,245 @@
+use std::error::Error;
+use std::ffi::OsString;
+use std::io::{BufRead, Write};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+use log::{info, warn};
+
+use crate::reader::DatasetMetadata;
+use crate::{
+    ComputedBoundaries, DatasetReader, DatasetWriter, Model, TrainingExample, VersionSpace,
+};
+
+/// Learns a version space by splitting the training examples into shards, learning each shard in
+/// its own worker process and merging the resulting version spaces.
+///
+/// Each worker is spawned from `program` and `args`, receives its shard on stdin in the format
+/// written by [`ShardedSolver::write_shard`] and must print the [`Model`] it learned as JSON to
+/// stdout. A shard whose worker crashes or exits unsuccessfully is retried on a fresh worker, up to
+/// `max_attempts` times in total, without affecting the other shards.
+pub struct ShardedSolver {
+    pub max_attempts: usize,
+    training_examples: Vec<TrainingExample>,
+    dataset_metadata: Arc<DatasetMetadata>,
+    n_shards: usize,
+    program: OsString,
+    args: Vec<OsString>,
+}
+
+impl ShardedSolver {
+    pub fn new<P, I, A>(
+        training_examples: Vec<TrainingExample>,
+        dataset_metadata: &Arc<DatasetMetadata>,
+        n_shards: usize,
+        program: P,
+        args: I,
+    ) -> Self
+    where
+        P: Into<OsString>,
+        I: IntoIterator<Item = A>,
+        A: Into<OsString>,
+    {
+        Self {
+            max_attempts: 3,
+            training_examples,
+            dataset_metadata: Arc::clone(dataset_metadata),
+            n_shards: n_shards.max(1),
+            program: program.into(),
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Learn every shard in parallel and merge their version spaces. Training examples are dealt
+    /// out to the shards in turn, so each shard sees a similar mix of examples.
+    pub fn solve(self) -> Result<ComputedBoundaries, Box<dyn Error>> {
+        let mut shards = vec![vec![]; self.n_shards];
+        for (index, example) in self.training_examples.into_iter().enumerate() {
+            shards[index % self.n_shards].push(example);
+        }
+        shards.retain(|shard| !shard.is_empty());
+
+        let models = std::thread::scope(|scope| {
+            let handles = shards
+                .iter()
+                .enumerate()
+                .map(|(index, shard)| {
+                    let worker = Worker {
+                        program: &self.program,
+                        args: &self.args,
+                        dataset_metadata: &self.dataset_metadata,
+                    };
+                    let max_attempts = self.max_attempts;
+                    scope.spawn(move || worker.learn_with_retries(index, shard, max_attempts))
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Result<Vec<Model>, String>>()
+        })?;
+
+        let mut boundaries = VersionSpace::new(&self.dataset_metadata).into_boundaries();
+        for model in models {
+            boundaries = boundaries.merge(&model.into_boundaries(&self.dataset_metadata)?)?;
+        }
+
+        Ok(boundaries)
+    }
+
+    /// Write a shard for a worker: the dataset metadata as a single line of JSON, followed by the
+    /// training examples as a dataset with generic headers
+    pub fn write_shard<W: Write + 'static>(
+        mut output: W,
+        metadata: &DatasetMetadata,
+        training_examples: &[TrainingExample],
+    ) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(&mut output, metadata)?;
+        writeln!(output)?;
+
+        let headers = DatasetWriter::default_headers(metadata);
+        let mut writer = DatasetWriter::from_writer(output, metadata, &headers)?;
+        for example in training_examples {
+            writer.write(example)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read a shard written by [`ShardedSolver::write_shard`], returning its metadata and a
+    /// reader over its training examples
+    pub fn read_shard<R: BufRead + Send + 'static>(
+        mut input: R,
+    ) -> Result<(DatasetMetadata, DatasetReader), Box<dyn Error>> {
+        let mut line = String::new();
+        input.read_line(&mut line)?;
+        let metadata: DatasetMetadata = serde_json::from_str(&line)?;
+
+        let reader = DatasetReader::from_reader(input, metadata.clone());
+        Ok((metadata, reader))
+    }
+}
+
+struct Worker<'s> {
+    program: &'s OsString,
+    args: &'s [OsString],
+    dataset_metadata: &'s DatasetMetadata,
+}
+
+impl Worker<'_> {
+    fn learn_with_retries(
+        &self,
+        index: usize,
+        shard: &[TrainingExample],
+        max_attempts: usize,
+    ) -> Result<Model, String> {
+        let mut attempt = 1;
+        loop {
+            info!(
+                "Learning shard {index} ({} training examples), attempt {attempt}",
+                shard.len()
+            );
+
+            match self.learn(shard) {
+                Ok(model) => return Ok(model),
+                Err(err) if attempt < max_attempts => {
+                    warn!("Worker for shard {index} failed, retrying: {err}");
+                    attempt += 1;
+                }
+                Err(err) => {
+                    return Err(format!(
+                        "Worker for shard {index} failed {attempt} times, giving up: {err}"
+                    ))
+                }
+            }
+        }
+    }
+
+    fn learn(&self, shard: &[TrainingExample]) -> Result<Model, Box<dyn Error>> {
+        let mut child = Command::new(self.program)
+            .args(self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        // Dropping stdin once written closes it, signalling the end of the shard. A worker that
+        // crashed early is reported by its exit status rather than by the failed write
+        let written =
+            ShardedSolver::write_shard(child.stdin.take().unwrap(), self.dataset_metadata, shard);
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(format!("worker exited with {}", output.status).into());
+        }
+        written?;
+
+        let model: Model = serde_json::from_slice(&output.stdout)?;
+        if model.fingerprint.n_examples != shard.len() {
+            return Err(format!(
+                "worker learned from {} training examples but the shard has {}",
+                model.fingerprint.n_examples,
+                shard.len()
+            )
+            .into());
+        }
+
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Cursor};
+
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+
+    #[test]
+    fn test_shard_round_trip() {
+        let examples = enjoysport_examples();
+
+        let path = std::env::temp_dir().join(format!("ccelm-{}-shard.csv", std::process::id()));
+        ShardedSolver::write_shard(
+            std::fs::File::create(&path).unwrap(),
+            &DATASET_METADATA,
+            &examples,
+        )
+        .unwrap();
+
+        let input = BufReader::new(Cursor::new(std::fs::read(&path).unwrap()));
+        let (metadata, reader) = ShardedSolver::read_shard(input).unwrap();
+
+        assert_eq!(metadata, **DATASET_METADATA);
+        assert_eq!(reader.collect::<Result<Vec<_>, _>>().unwrap(), examples);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_failing_worker_is_retried_then_reported() {
+        let counter = std::env::temp_dir().join(format!("ccelm-{}-attempts", std::process::id()));
+        let script = format!(
+            "cat > /dev/null; echo attempt >> {}; exit 1",
+            counter.display()
+        );
+
+        let mut solver = ShardedSolver::new(
+            enjoysport_examples(),
+            &DATASET_METADATA,
+            1,
+            "sh",
+            ["-c", script.as_str()],
+        );
+        solver.max_attempts = 2;
+
+        let err = solver.solve().unwrap_err();
+        assert!(err.to_string().contains("failed 2 times"));
+        assert_eq!(
+            std::fs::read_to_string(&counter).unwrap().lines().count(),
+            2
+        );
+
+        std::fs::remove_file(counter).unwrap();
+    }
+}
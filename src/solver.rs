@@ -1,86 +1,446 @@
-use log::{info, trace};
+use std::convert::Infallible;
+use std::error::Error;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Instant;
 
+use log::info;
+
+use crate::learner::{learn, Learner};
 use crate::reader::DatasetMetadata;
-use crate::{ComputedBoundaries, Hypothesis, TrainingExample};
+use crate::version_space::Specializer;
+use crate::{
+    Budget, BudgetExceeded, Checkpointer, ComputedBoundaries, DiskBoundary, Hypothesis,
+    HypothesisArena, HypothesisSpace, TrainingExample, VersionSpace,
+};
 
 #[derive(Clone, Debug)]
-pub struct Solver<'a> {
-    pub specific_boundary: Hypothesis<'a>,
-    pub general_boundary: Vec<Hypothesis<'a>>,
+pub struct Solver {
+    pub specific_boundary: Hypothesis,
+    pub general_boundary: HypothesisSpace,
+    pub checkpointer: Option<Checkpointer>,
+    pub interrupt: Option<Arc<AtomicBool>>,
+    pub budget: Budget,
     training_examples: Vec<TrainingExample>,
-    dataset_metadata: &'a DatasetMetadata,
+    dataset_metadata: Arc<DatasetMetadata>,
 }
 
-impl<'a> Solver<'a> {
+impl Solver {
     pub fn new(
         training_examples: Vec<TrainingExample>,
-        dataset_metadata: &'a DatasetMetadata,
+        dataset_metadata: &Arc<DatasetMetadata>,
     ) -> Self {
         let attribute_count = dataset_metadata.columns.len();
         Self {
             specific_boundary: Hypothesis::specific(attribute_count, dataset_metadata),
-            general_boundary: vec![Hypothesis::general(attribute_count, dataset_metadata)],
+            general_boundary: HypothesisSpace::maximal(
+                [Hypothesis::general(attribute_count, dataset_metadata)],
+                dataset_metadata,
+            ),
+            checkpointer: None,
+            interrupt: None,
+            budget: Budget::default(),
             training_examples,
-            dataset_metadata,
+            dataset_metadata: Arc::clone(dataset_metadata),
         }
     }
 
-    pub fn solve(mut self) -> ComputedBoundaries<'a> {
-        let n_training_examples = self.training_examples.len();
-        let column_data = &self.dataset_metadata.columns;
-        for (index, example) in self.training_examples.into_iter().enumerate() {
-            info!(
-                "{index}/{n_training_examples} | {} general hypotheses",
-                self.general_boundary.len()
-            );
-
-            if example.is_positive {
-                info!("Processing positive training example: {example}");
-                // Remove any hypothesis that is inconsistent with d
-                self.general_boundary
-                    .retain(|hypothesis| hypothesis.is_consistent(&example));
-
-                trace!("Inconsistent hypotheses removed from general boundary");
-
-                self.specific_boundary = self.specific_boundary.generalize(&example);
-
-                trace!("Specific hypothesis barrier refined");
-            } else {
-                info!("Processing negative training example: {example}");
-                // Remove any hypothesis that is inconsistent with d
-                //specific_hypothesis.retain(|hypothesis| hypothesis.is_consistent(&example));
-
-                let mut new_hypotheses = vec![];
-                for hypothesis in self.general_boundary.into_iter() {
-                    if hypothesis.is_consistent(&example) {
-                        new_hypotheses.push(hypothesis);
-                        continue;
-                    }
+    pub fn solve(self) -> ComputedBoundaries {
+        let Ok(boundaries) = self.solve_stream(std::iter::empty::<Result<_, Infallible>>());
+        boundaries
+    }
+
+    /// Learn from a stream of training examples without buffering them, e.g. rows as they are
+    /// read from a [`crate::DatasetReader`]. Any training examples passed to [`Solver::new`] are
+    /// processed first. Returns the first error in the stream.
+    ///
+    /// If a [`Checkpointer`] is set, checkpoints are saved while solving. Failing to save a
+    /// checkpoint is logged rather than aborting the solve.
+    ///
+    /// If the interrupt flag is raised, solving stops once the current training example has been
+    /// processed and the boundaries learned so far are returned, flagged as interrupted. The same
+    /// happens when the [`Budget`] is exceeded without a beam to fall back on.
+    pub fn solve_stream<I, E>(mut self, training_examples: I) -> Result<ComputedBoundaries, E>
+    where
+        I: IntoIterator<Item = Result<TrainingExample, E>>,
+    {
+        let version_space = VersionSpace::from_boundaries(
+            ComputedBoundaries {
+                specific_boundary: Some(self.specific_boundary),
+                general_boundary: self.general_boundary.to_vec(),
+                interrupted_after: None,
+                budget_exceeded: None,
+                is_approximate: false,
+            },
+            &self.dataset_metadata,
+        );
+
+        let training_examples = self
+            .training_examples
+            .into_iter()
+            .map(Ok)
+            .chain(training_examples);
+
+        learn(
+            version_space,
+            training_examples,
+            self.checkpointer.as_mut(),
+            &self.interrupt,
+            &self.budget,
+        )
+    }
+
+    /// Like [`Solver::solve_stream`], but keeping the general boundary in a [`DiskBoundary`]
+    /// inside `directory` rather than in memory, so that it can grow beyond the available RAM.
+    /// Each training example streams the general boundary from one set of run files into the
+    /// next, and only the final general boundary is read back into memory.
+    ///
+    /// Narrowing to a beam or removing hypotheses that aren't maximally general needs the general
+    /// boundary in memory, so exceeding the [`Budget`] always aborts. The general boundary is only
+    /// pruned within each run of up to `chunk_size` hypotheses while learning, and as a whole once
+    /// it has been read back. Saving a checkpoint reads the general boundary into
+    /// memory while it is written.
+    pub fn solve_stream_on_disk<I, E>(
+        mut self,
+        directory: &Path,
+        chunk_size: usize,
+        training_examples: I,
+    ) -> Result<ComputedBoundaries, Box<dyn Error>>
+    where
+        I: IntoIterator<Item = Result<TrainingExample, E>>,
+        E: Into<Box<dyn Error>>,
+    {
+        let version_space = DiskVersionSpace {
+            general_boundary: DiskBoundary::new(
+                directory,
+                chunk_size,
+                self.general_boundary.iter(),
+                &self.dataset_metadata,
+            )?,
+            specific_boundary: self.specific_boundary,
+            dataset_metadata: Arc::clone(&self.dataset_metadata),
+        };
+
+        let training_examples = self.training_examples.into_iter().map(Ok).chain(
+            training_examples
+                .into_iter()
+                .map(|example| example.map_err(Into::into)),
+        );
 
-                    let mut specializations =
-                        hypothesis.specialize(&example, column_data.as_slice());
-                    specializations.retain(|specialization| {
-                        specialization.is_more_general(&self.specific_boundary)
-                    });
-                    new_hypotheses.extend(specializations);
+        learn(
+            version_space,
+            training_examples,
+            self.checkpointer.as_mut(),
+            &self.interrupt,
+            &self.budget,
+        )
+    }
+}
+
+impl<E> Learner<E> for VersionSpace {
+    fn general_boundary_len(&self) -> usize {
+        VersionSpace::general_boundary_len(self)
+    }
+
+    fn add_example(&mut self, example: TrainingExample) -> Result<(), E> {
+        VersionSpace::add_example(self, example);
+        Ok(())
+    }
+
+    fn enforce_budget(
+        &mut self,
+        budget: &Budget,
+        started: Instant,
+        n_examples: usize,
+    ) -> Result<bool, BudgetExceeded> {
+        VersionSpace::enforce_budget(self, budget, started, n_examples)
+    }
+
+    fn save_checkpoint(
+        &self,
+        checkpointer: &Checkpointer,
+        is_approximate: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        VersionSpace::save_checkpoint(self, checkpointer, is_approximate)
+    }
+
+    fn into_boundaries(self) -> Result<ComputedBoundaries, E> {
+        Ok(VersionSpace::into_boundaries(self))
+    }
+}
+
+/// The version space of [`Solver::solve_stream_on_disk`], whose general boundary is kept on disk
+struct DiskVersionSpace {
+    specific_boundary: Hypothesis,
+    general_boundary: DiskBoundary,
+    dataset_metadata: Arc<DatasetMetadata>,
+}
+
+impl Learner<Box<dyn Error>> for DiskVersionSpace {
+    fn general_boundary_len(&self) -> usize {
+        self.general_boundary.len()
+    }
+
+    fn add_example(&mut self, example: TrainingExample) -> Result<(), Box<dyn Error>> {
+        if example.is_positive {
+            info!("Processing positive training example: {example}");
+            self.general_boundary
+                .retain(|hypothesis| hypothesis.classify(&example))?;
+            self.specific_boundary = self.specific_boundary.generalize(&example);
+        } else {
+            info!("Processing negative training example: {example}");
+
+            // Each batch read back is specialized like the in-memory general boundary is, so
+            // duplicates collapse and hypotheses subsumed by others in the same batch are pruned
+            // before they are written out again
+            let column_data = &self.dataset_metadata.columns;
+            let specializer = Specializer::new(&example, &self.specific_boundary, column_data);
+            let dataset_metadata = &self.dataset_metadata;
+            self.general_boundary.flat_map_batches(|batch| {
+                let mut covering = HypothesisArena::new(column_data.len());
+                let mut kept = HypothesisSpace::new(dataset_metadata);
+                for hypothesis in batch {
+                    if hypothesis.classify(&example) {
+                        covering.intern(&hypothesis.attributes);
+                    } else {
+                        kept.insert(hypothesis);
+                    }
                 }
 
-                self.general_boundary = new_hypotheses;
-            }
+                kept.extend_maximal(&specializer.specialize_all(&covering));
+                kept.to_vec()
+            })?;
+        }
 
-            info!("Successfully processed example");
+        info!("Successfully processed example");
+        Ok(())
+    }
+
+    /// Narrowing to a beam needs the general boundary in memory, so this never narrows and only
+    /// fails if the budget is exceeded
+    fn enforce_budget(
+        &mut self,
+        budget: &Budget,
+        started: Instant,
+        n_examples: usize,
+    ) -> Result<bool, BudgetExceeded> {
+        match budget.check(
+            started,
+            n_examples,
+            self.general_boundary.len(),
+            self.dataset_metadata.columns.len(),
+        ) {
+            Some(exceeded) => Err(exceeded),
+            None => Ok(false),
         }
+    }
+
+    fn save_checkpoint(
+        &self,
+        checkpointer: &Checkpointer,
+        is_approximate: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        checkpointer.save(
+            &self.specific_boundary,
+            &self.general_boundary.to_vec()?,
+            is_approximate,
+            &self.dataset_metadata,
+        )
+    }
 
-        let cloned_boundary = self.general_boundary.clone();
-        self.general_boundary.retain(|hypothesis| {
-            cloned_boundary
-                .iter()
-                .all(|other| hypothesis == other || !hypothesis.is_more_general(other))
-        });
+    /// Read the general boundary back into memory, removing any hypotheses that are less general
+    /// than others from a different run
+    fn into_boundaries(mut self) -> Result<ComputedBoundaries, Box<dyn Error>> {
+        let version_space = VersionSpace::from_boundaries(
+            ComputedBoundaries {
+                specific_boundary: Some(self.specific_boundary),
+                general_boundary: self.general_boundary.take_all()?,
+                ..Default::default()
+            },
+            &self.dataset_metadata,
+        );
 
-        ComputedBoundaries {
-            specific_boundary: Some(self.specific_boundary),
-            general_boundary: self.general_boundary,
+        Ok(version_space.into_boundaries())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::DatasetReader;
+
+    #[test]
+    fn test_general_boundary_is_maximally_general() {
+        // The second example is only covered by ⟨Rainy, ?, ?, ?, ?, ?⟩, whose specializations are
+        // all subsumed by the other members of the general boundary
+        let training_examples = [
+            "Sunny,Warm,Normal,Strong,Warm,Same,false",
+            "Rainy,Warm,Normal,Strong,Warm,Same,false",
+        ]
+        .into_iter()
+        .map(|record| TrainingExample::from_str(record, &DATASET_METADATA).unwrap())
+        .collect();
+
+        let boundaries = Solver::new(training_examples, &DATASET_METADATA).solve();
+        let mut general_boundary = boundaries
+            .general_boundary
+            .iter()
+            .map(|hypothesis| hypothesis.to_string())
+            .collect::<Vec<String>>();
+        general_boundary.sort();
+
+        let mut expected = [
+            "⟨Cloudy, ?, ?, ?, ?, ?⟩",
+            "⟨?, Cold, ?, ?, ?, ?⟩",
+            "⟨?, ?, High, ?, ?, ?⟩",
+            "⟨?, ?, ?, Weak, ?, ?⟩",
+            "⟨?, ?, ?, ?, Cool, ?⟩",
+            "⟨?, ?, ?, ?, ?, Change⟩",
+        ];
+        expected.sort();
+        assert_eq!(general_boundary, expected);
+    }
+
+    #[test]
+    fn test_solve_stream_from_reader() {
+        let dataset = std::fs::read("./data/enjoysport/dataset.csv").unwrap();
+        let reader = DatasetReader::from_reader(
+            std::io::Cursor::new(dataset),
+            DatasetMetadata::clone(&DATASET_METADATA),
+        );
+
+        let streamed = Solver::new(vec![], &DATASET_METADATA)
+            .solve_stream(reader)
+            .unwrap();
+
+        let training_examples = enjoysport_examples();
+        let buffered = Solver::new(training_examples, &DATASET_METADATA).solve();
+
+        assert_eq!(streamed.specific_boundary, buffered.specific_boundary);
+        assert_eq!(streamed.general_boundary, buffered.general_boundary);
+    }
+
+    #[test]
+    fn test_resume_from_boundaries() {
+        let mut training_examples = enjoysport_examples();
+
+        let full = Solver::new(training_examples.clone(), &DATASET_METADATA).solve();
+
+        let remaining = training_examples.split_off(2);
+        let partial = Solver::new(training_examples, &DATASET_METADATA).solve();
+
+        let mut solver = Solver::new(remaining, &DATASET_METADATA);
+        solver.specific_boundary = partial.specific_boundary.unwrap();
+        solver.general_boundary =
+            HypothesisSpace::maximal(partial.general_boundary, &DATASET_METADATA);
+        let resumed = solver.solve();
+
+        assert_eq!(resumed.specific_boundary, full.specific_boundary);
+        assert_eq!(resumed.general_boundary, full.general_boundary);
+    }
+
+    #[test]
+    fn test_solve_stream_stops_at_error() {
+        let dataset = "sky,air_temp,humidity,wind,water,forecast,enjoy_sport\n\
+                       Sunny,Warm,Normal,Strong,Warm,Same,true\n\
+                       Sunny,Warm,High,Strong,Warm,Same,maybe\n";
+        let reader = DatasetReader::from_reader(
+            dataset.as_bytes(),
+            DatasetMetadata::clone(&DATASET_METADATA),
+        );
+
+        assert!(Solver::new(vec![], &DATASET_METADATA)
+            .solve_stream(reader)
+            .is_err());
+    }
+
+    #[test]
+    fn test_interrupted_solve_returns_partial_boundaries() {
+        let training_examples = enjoysport_examples();
+
+        let mut solver = Solver::new(training_examples.clone(), &DATASET_METADATA);
+        solver.interrupt = Some(Arc::new(AtomicBool::new(true)));
+        let partial = solver.solve();
+
+        let first = Solver::new(training_examples[..1].to_vec(), &DATASET_METADATA).solve();
+
+        assert_eq!(partial.interrupted_after, Some(1));
+        assert_eq!(partial.specific_boundary, first.specific_boundary);
+        assert_eq!(partial.general_boundary, first.general_boundary);
+        assert_eq!(first.interrupted_after, None);
+    }
+
+    #[test]
+    fn test_solve_on_disk_matches_memory() {
+        let training_examples = enjoysport_examples();
+
+        let in_memory = Solver::new(training_examples.clone(), &DATASET_METADATA).solve();
+        let on_disk = Solver::new(training_examples, &DATASET_METADATA)
+            .solve_stream_on_disk(
+                &std::env::temp_dir(),
+                2,
+                std::iter::empty::<Result<_, Infallible>>(),
+            )
+            .unwrap();
+
+        assert_eq!(on_disk.specific_boundary, in_memory.specific_boundary);
+        assert_eq!(on_disk.general_boundary, in_memory.general_boundary);
+    }
+
+    #[test]
+    fn test_solve_on_disk_matches_memory_with_missing_values() {
+        // The Boundaries datasets are full of missing values, which the general boundary is
+        // specialized against before the on-disk solver prunes it
+        let metadata: Arc<DatasetMetadata> = Arc::new(
+            serde_yaml::from_reader(
+                std::fs::File::open("./data/boundaries-germanic/metadata.yaml").unwrap(),
+            )
+            .unwrap(),
+        );
+        let training_examples = DatasetReader::new(
+            "./data/boundaries-germanic/dataset.tsv",
+            DatasetMetadata::clone(&metadata),
+        )
+        .unwrap()
+        .take(8)
+        .collect::<Result<Vec<TrainingExample>, _>>()
+        .unwrap();
+
+        let in_memory = Solver::new(training_examples.clone(), &metadata).solve();
+        let on_disk = Solver::new(training_examples, &metadata)
+            .solve_stream_on_disk(
+                &std::env::temp_dir(),
+                64,
+                std::iter::empty::<Result<_, Infallible>>(),
+            )
+            .unwrap();
+
+        assert!(!in_memory.general_boundary.is_empty());
+        assert_eq!(on_disk.specific_boundary, in_memory.specific_boundary);
+        assert_eq!(on_disk.general_boundary, in_memory.general_boundary);
+    }
+
+    #[test]
+    fn test_boundaries_outlive_metadata() {
+        fn learn() -> ComputedBoundaries {
+            let metadata = Arc::new(DatasetMetadata::clone(&DATASET_METADATA));
+            let training_examples =
+                DatasetReader::new("./data/enjoysport/dataset.csv", metadata.as_ref().clone())
+                    .unwrap()
+                    .collect::<Result<Vec<TrainingExample>, _>>()
+                    .unwrap();
+            Solver::new(training_examples, &metadata).solve()
         }
+
+        // The boundaries own their metadata, so they can be returned and sent to another thread
+        let boundaries = learn();
+        let specific_boundary =
+            std::thread::spawn(move || boundaries.specific_boundary.unwrap().to_string())
+                .join()
+                .unwrap();
+
+        assert_eq!(specific_boundary, "⟨Sunny, Warm, ?, Strong, ?, ?⟩");
     }
 }
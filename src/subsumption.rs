@@ -0,0 +1,148 @@
+use itertools::Itertools;
+
+use crate::{Attribute, Hypothesis};
+
+/// An inverted index over a set of hypotheses that finds the members more general than a given
+/// hypothesis without comparing it to each of them in turn.
+///
+/// For every attribute, the index holds one bitset of the members that accept any value there and
+/// one bitset per value of the members that require it. A member is more general than `h` exactly
+/// when, for every attribute of `h`, it is in the bitset of that value or in the any-value bitset,
+/// so a query is the intersection of those bitsets, 64 members at a time.
+#[derive(Clone, Debug)]
+pub struct SubsumptionIndex {
+    len: usize,
+    any: Vec<Bitset>,
+    values: Vec<Vec<Bitset>>,
+}
+
+type Bitset = Vec<u64>;
+
+impl SubsumptionIndex {
+    pub fn new(hypotheses: &[Hypothesis]) -> Self {
+        let n_attributes = hypotheses.first().map_or(0, Hypothesis::len);
+        let n_words = hypotheses.len().div_ceil(64);
+
+        let mut any = vec![vec![0; n_words]; n_attributes];
+        let mut values: Vec<Vec<Bitset>> = vec![vec![]; n_attributes];
+        for (index, hypothesis) in hypotheses.iter().enumerate() {
+            let (word, bit) = (index / 64, 1 << (index % 64));
+            for (attribute_index, attribute) in hypothesis.attributes.iter().enumerate() {
+                match attribute {
+                    Attribute::Any => any[attribute_index][word] |= bit,
+                    Attribute::Value(value) => {
+                        let value_bitsets = &mut values[attribute_index];
+                        let value = usize::from(*value);
+                        if value_bitsets.len() <= value {
+                            value_bitsets.resize(value + 1, vec![0; n_words]);
+                        }
+                        value_bitsets[value][word] |= bit;
+                    }
+                    // A member that accepts no value is only more general than hypotheses that
+                    // don't either, which are matched by every member
+                    Attribute::NoValue => {}
+                }
+            }
+        }
+
+        Self {
+            len: hypotheses.len(),
+            any,
+            values,
+        }
+    }
+
+    /// The indices of the members that are more general than or equal to `hypothesis`
+    pub fn more_general(&self, hypothesis: &Hypothesis) -> impl Iterator<Item = usize> + '_ {
+        let constraints = hypothesis
+            .attributes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, attribute)| match attribute {
+                Attribute::Any => Some((&self.any[index], None)),
+                Attribute::Value(value) => Some((
+                    &self.any[index],
+                    self.values[index].get(usize::from(*value)),
+                )),
+                Attribute::NoValue => None,
+            })
+            .collect_vec();
+
+        (0..self.len.div_ceil(64)).flat_map(move |word| {
+            let mut matches = if (word + 1) * 64 <= self.len {
+                u64::MAX
+            } else {
+                (1 << (self.len % 64)) - 1
+            };
+            for (any, value) in constraints.iter() {
+                if matches == 0 {
+                    break;
+                }
+                matches &= any[word] | value.map_or(0, |value| value[word]);
+            }
+
+            (0..64)
+                .filter(move |bit| matches & (1 << bit) != 0)
+                .map(move |bit| word * 64 + bit)
+        })
+    }
+
+    /// Whether the member at `index` is subsumed by a different member, i.e. whether another
+    /// member is more general than or equal to it
+    pub fn is_subsumed(&self, index: usize, hypothesis: &Hypothesis) -> bool {
+        self.more_general(hypothesis).any(|other| other != index)
+    }
+}
+
+/// Only keep the maximally general hypotheses, once each
+pub fn retain_maximal(hypotheses: &mut Vec<Hypothesis>) {
+    *hypotheses = std::mem::take(hypotheses).into_iter().unique().collect();
+
+    let index = SubsumptionIndex::new(hypotheses);
+    let is_subsumed = hypotheses
+        .iter()
+        .enumerate()
+        .map(|(position, hypothesis)| index.is_subsumed(position, hypothesis))
+        .collect_vec();
+
+    let mut is_subsumed = is_subsumed.into_iter();
+    hypotheses.retain(|_| !is_subsumed.next().unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::DATASET_METADATA;
+
+    #[test]
+    fn test_retain_maximal() {
+        let hypothesis = |record| Hypothesis::from_str(record, &DATASET_METADATA).unwrap();
+        let mut hypotheses = vec![
+            hypothesis("Sunny,?,?,?,?,?"),
+            hypothesis("Sunny,Warm,?,?,?,?"),
+            hypothesis("?,Warm,?,?,?,?"),
+            hypothesis("Sunny,?,?,?,?,?"),
+            hypothesis("Rainy,?,?,Strong,?,?"),
+            hypothesis("?,Cold,?,?,?,Same"),
+        ];
+
+        let index = SubsumptionIndex::new(&hypotheses);
+        assert_eq!(
+            index
+                .more_general(&hypothesis("Sunny,Warm,High,?,?,?"))
+                .collect_vec(),
+            vec![0, 1, 2, 3]
+        );
+
+        retain_maximal(&mut hypotheses);
+        assert_eq!(
+            hypotheses,
+            vec![
+                hypothesis("Sunny,?,?,?,?,?"),
+                hypothesis("?,Warm,?,?,?,?"),
+                hypothesis("Rainy,?,?,Strong,?,?"),
+                hypothesis("?,Cold,?,?,?,Same"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,26 @@
+//! Fixtures shared by the unit tests of several modules
+
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+
+use crate::{DatasetMetadata, DatasetReader, TrainingExample};
+
+lazy_static! {
+    /// The metadata of `data/enjoysport`
+    pub static ref DATASET_METADATA: Arc<DatasetMetadata> = Arc::new(
+        serde_yaml::from_reader(std::fs::File::open("./data/enjoysport/metadata.yaml").unwrap())
+            .unwrap()
+    );
+}
+
+/// Every training example of `data/enjoysport`, in order
+pub fn enjoysport_examples() -> Vec<TrainingExample> {
+    DatasetReader::new(
+        "./data/enjoysport/dataset.csv",
+        DatasetMetadata::clone(&DATASET_METADATA),
+    )
+    .unwrap()
+    .collect::<Result<Vec<TrainingExample>, _>>()
+    .unwrap()
+}
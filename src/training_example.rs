@@ -11,8 +11,8 @@ pub struct TrainingExample {
     pub is_positive: bool,
 }
 
-impl From<Hypothesis<'_>> for TrainingExample {
-    fn from(hypothesis: Hypothesis<'_>) -> Self {
+impl From<Hypothesis> for TrainingExample {
+    fn from(hypothesis: Hypothesis) -> Self {
         TrainingExample {
             attributes: hypothesis.attributes,
             is_positive: true,
@@ -41,6 +41,21 @@ impl TrainingExample {
         bytes
     }
 
+    /// Convert each of the attributes to the string used to represent it in the dataset itself,
+    /// followed by the label
+    pub fn to_dataset_vec(&self, dataset_metadata: &DatasetMetadata) -> Vec<String> {
+        let mut values: Vec<String> = self
+            .attributes
+            .iter()
+            .enumerate()
+            .map(|(index, attribute)| attribute.to_dataset_string(index, dataset_metadata))
+            .collect();
+
+        values.push(self.is_positive.to_string());
+
+        values
+    }
+
     pub fn from_str(
         record: &str,
         dataset_metadata: &DatasetMetadata,
@@ -0,0 +1,164 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::reader::DatasetMetadata;
+use crate::{Attribute, ComputedBoundaries, ConcurrentSolver, Hypothesis, Solver, TrainingExample};
+
+/// How often a value of a randomly generated training example is missing, and so read as a
+/// wildcard like the missing values of the Boundaries data
+const MISSING_RATE: f64 = 0.1;
+
+/// How many hypotheses [`verify_solvers`] stores in each run file of the on-disk solver. This is
+/// kept small so that larger general boundaries span several runs, but not so small that pruning
+/// only within each run lets the general boundary grow much larger than in memory.
+const DISK_CHUNK_SIZE: usize = 1024;
+
+/// Learn the training examples with [`Solver`], with [`ConcurrentSolver`] for each of the given
+/// thread counts and with [`Solver::solve_stream_on_disk`], and check that every run computes the
+/// same boundaries. Returns the boundaries they agree on, or an error listing the hypotheses only
+/// one of them found.
+pub fn verify_solvers(
+    training_examples: &[TrainingExample],
+    metadata: &Arc<DatasetMetadata>,
+    thread_counts: &[usize],
+) -> Result<ComputedBoundaries, Box<dyn Error>> {
+    let expected = Solver::new(training_examples.to_vec(), metadata).solve();
+
+    let compare = |name: &str, actual: ComputedBoundaries| -> Result<(), Box<dyn Error>> {
+        if actual.specific_boundary != expected.specific_boundary {
+            return Err(format!(
+                "The specific boundaries differ with {name}\nSolver: {}\n{name}: {}",
+                expected.specific_boundary.iter().join(", "),
+                actual.specific_boundary.iter().join(", ")
+            )
+            .into());
+        }
+
+        if actual.general_boundary != expected.general_boundary {
+            let difference = |left: &[Hypothesis], right: &[Hypothesis]| {
+                left.iter()
+                    .filter(|hypothesis| !right.contains(hypothesis))
+                    .join("\n")
+            };
+
+            return Err(format!(
+                "The general boundaries differ with {name}\n----------Only found by Solver----------\n{}\n----------Only found by {name}----------\n{}",
+                difference(&expected.general_boundary, &actual.general_boundary),
+                difference(&actual.general_boundary, &expected.general_boundary)
+            )
+            .into());
+        }
+
+        Ok(())
+    };
+
+    for &n_threads in thread_counts {
+        compare(
+            &format!("ConcurrentSolver with {n_threads} threads"),
+            ConcurrentSolver::new(training_examples.to_vec(), metadata, n_threads).solve(),
+        )?;
+    }
+
+    compare(
+        "the on-disk Solver",
+        Solver::new(training_examples.to_vec(), metadata).solve_stream_on_disk(
+            &std::env::temp_dir(),
+            DISK_CHUNK_SIZE,
+            std::iter::empty::<Result<_, Infallible>>(),
+        )?,
+    )?;
+
+    Ok(expected)
+}
+
+/// Generate a dataset of `n_examples` training examples over `n_attributes` attributes that each
+/// take one of `n_values` values, along with its metadata.
+///
+/// The examples are labelled by a hidden target concept, so the version space doesn't collapse
+/// after the first few of them. Some values are then made missing, which the solvers read as
+/// wildcards. Fails if `n_values` is more than the 256 values an attribute can take.
+pub fn random_dataset(
+    n_attributes: usize,
+    n_values: usize,
+    n_examples: usize,
+    seed: u64,
+) -> Result<(DatasetMetadata, Vec<TrainingExample>), Box<dyn Error>> {
+    let max_values = usize::from(u8::MAX) + 1;
+    if n_values > max_values {
+        return Err(format!("Attributes can't take more than {max_values} values").into());
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let columns = (0..n_attributes)
+        .map(|_| (0..n_values).map(|value| format!("v{value}")).collect())
+        .collect();
+    let metadata = DatasetMetadata::new(columns, None, None, ',');
+
+    let random_value =
+        |rng: &mut StdRng| Attribute::Value(u8::try_from(rng.gen_range(0..n_values)).unwrap());
+
+    let target = (0..n_attributes)
+        .map(|_| {
+            if rng.gen_bool(0.5) {
+                Attribute::Any
+            } else {
+                random_value(&mut rng)
+            }
+        })
+        .collect::<Vec<Attribute>>();
+
+    let training_examples = (0..n_examples)
+        .map(|_| {
+            let mut attributes = (0..n_attributes)
+                .map(|_| random_value(&mut rng))
+                .collect::<Vec<Attribute>>();
+            let is_positive = target
+                .iter()
+                .zip(attributes.iter())
+                .all(|(target, attribute)| *target == Attribute::Any || target == attribute);
+
+            for attribute in attributes.iter_mut() {
+                if rng.gen_bool(MISSING_RATE) {
+                    *attribute = Attribute::Any;
+                }
+            }
+
+            TrainingExample::new(&attributes, is_positive)
+        })
+        .collect();
+
+    Ok((metadata, training_examples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_dataset_is_reproducible() {
+        let (metadata, training_examples) = random_dataset(5, 3, 20, 7).unwrap();
+
+        assert_eq!(metadata.columns.len(), 5);
+        assert_eq!(training_examples.len(), 20);
+        assert_eq!(random_dataset(5, 3, 20, 7).unwrap().1, training_examples);
+        assert_ne!(random_dataset(5, 3, 20, 8).unwrap().1, training_examples);
+        assert!(random_dataset(5, 257, 20, 7).is_err());
+    }
+
+    #[test]
+    fn test_verify_solvers_agree_on_random_datasets() {
+        for seed in 0..5 {
+            let (metadata, training_examples) = random_dataset(6, 3, 15, seed).unwrap();
+
+            let boundaries =
+                verify_solvers(&training_examples, &Arc::new(metadata), &[1, 3]).unwrap();
+            assert!(boundaries.is_canonical());
+        }
+    }
+}
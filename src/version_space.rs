@@ -0,0 +1,250 @@
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Instant;
+
+use log::{info, trace};
+
+use crate::reader::DatasetMetadata;
+use crate::{
+    Attribute, Budget, BudgetExceeded, Checkpointer, ComputedBoundaries, Hypothesis,
+    HypothesisArena, HypothesisSpace, TrainingExample,
+};
+
+/// The step of candidate elimination that every solver takes for a negative training example:
+/// each member of the general boundary that covers the example is replaced by its minimal
+/// specializations that exclude it, keeping only those that are still more general than the
+/// specific boundary. Which members cover the example, and how the specializations are added back
+/// to the general boundary, is up to the solver.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Specializer<'a> {
+    example: &'a TrainingExample,
+    specific_boundary: &'a Hypothesis,
+    column_data: &'a [Vec<String>],
+}
+
+impl<'a> Specializer<'a> {
+    pub fn new(
+        example: &'a TrainingExample,
+        specific_boundary: &'a Hypothesis,
+        column_data: &'a [Vec<String>],
+    ) -> Self {
+        Self {
+            example,
+            specific_boundary,
+            column_data,
+        }
+    }
+
+    /// Intern the specializations of a single covering member into `specializations`
+    pub fn specialize_into(&self, member: &[Attribute], specializations: &mut HypothesisArena) {
+        specializations.specialize(member, self.example, self.column_data, |specialization| {
+            self.specific_boundary.is_more_specific_than(specialization)
+        });
+    }
+
+    /// The specializations of every member in `covering`, each interned once
+    pub fn specialize_all(&self, covering: &HypothesisArena) -> HypothesisArena {
+        let mut specializations = HypothesisArena::new(self.column_data.len());
+        for id in covering.ids() {
+            self.specialize_into(covering.get(id), &mut specializations);
+        }
+        specializations
+    }
+}
+
+/// A live version space that can be refined one training example at a time, rather than being
+/// learned from a complete set of training examples in one go
+#[derive(Clone, Debug)]
+pub struct VersionSpace {
+    specific_boundary: Hypothesis,
+    general_boundary: HypothesisSpace,
+    dataset_metadata: Arc<DatasetMetadata>,
+}
+
+impl VersionSpace {
+    /// Create a version space that contains every hypothesis, i.e. one that hasn't seen any
+    /// training examples yet
+    pub fn new(dataset_metadata: &Arc<DatasetMetadata>) -> Self {
+        let attribute_count = dataset_metadata.columns.len();
+        Self {
+            specific_boundary: Hypothesis::specific(attribute_count, dataset_metadata),
+            general_boundary: HypothesisSpace::maximal(
+                [Hypothesis::general(attribute_count, dataset_metadata)],
+                dataset_metadata,
+            ),
+            dataset_metadata: Arc::clone(dataset_metadata),
+        }
+    }
+
+    /// Continue from previously computed boundaries
+    pub fn from_boundaries(
+        boundaries: ComputedBoundaries,
+        dataset_metadata: &Arc<DatasetMetadata>,
+    ) -> Self {
+        let attribute_count = dataset_metadata.columns.len();
+        Self {
+            specific_boundary: boundaries
+                .specific_boundary
+                .unwrap_or_else(|| Hypothesis::specific(attribute_count, dataset_metadata)),
+            general_boundary: HypothesisSpace::maximal(
+                boundaries.general_boundary,
+                dataset_metadata,
+            ),
+            dataset_metadata: Arc::clone(dataset_metadata),
+        }
+    }
+
+    /// Refine the boundaries so that every hypothesis they enclose is consistent with the example
+    pub fn add_example(&mut self, example: TrainingExample) {
+        if example.is_positive {
+            info!("Processing positive training example: {example}");
+            // Remove any hypothesis that is inconsistent with d
+            self.general_boundary.retain_covering(&example);
+
+            trace!("Inconsistent hypotheses removed from general boundary");
+
+            self.specific_boundary = self.specific_boundary.generalize(&example);
+
+            trace!("Specific hypothesis barrier refined");
+        } else {
+            info!("Processing negative training example: {example}");
+
+            // Only the hypotheses that cover the example need to be specialized. Specializations
+            // are interned so duplicates collapse, and those that are subsumed by other members
+            // are pruned as they are inserted.
+            let column_data = &self.dataset_metadata.columns;
+            let mut covering = HypothesisArena::new(column_data.len());
+            self.general_boundary
+                .take_covering_into(&example, &mut covering);
+
+            let specializations = Specializer::new(&example, &self.specific_boundary, column_data)
+                .specialize_all(&covering);
+            self.general_boundary.extend_maximal(&specializations);
+        }
+
+        info!("Successfully processed example");
+    }
+
+    /// The number of hypotheses currently held in the general boundary
+    pub fn general_boundary_len(&self) -> usize {
+        self.general_boundary.len()
+    }
+
+    /// Save the version space as it currently is
+    pub fn save_checkpoint(
+        &self,
+        checkpointer: &Checkpointer,
+        is_approximate: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        checkpointer.save(
+            &self.specific_boundary,
+            &self.general_boundary.to_vec(),
+            is_approximate,
+            &self.dataset_metadata,
+        )
+    }
+
+    /// Check the general boundary against a budget for a solve that started at `started` and
+    /// has processed `n_examples` training examples. See [`Budget::enforce`].
+    pub fn enforce_budget(
+        &mut self,
+        budget: &Budget,
+        started: Instant,
+        n_examples: usize,
+    ) -> Result<bool, BudgetExceeded> {
+        budget.enforce(started, n_examples, &mut self.general_boundary)
+    }
+
+    /// The current specific and general boundaries
+    pub fn boundaries(&self) -> ComputedBoundaries {
+        self.clone().into_boundaries()
+    }
+
+    /// Consume the version space, returning its specific and general boundaries
+    pub fn into_boundaries(self) -> ComputedBoundaries {
+        ComputedBoundaries {
+            specific_boundary: Some(self.specific_boundary),
+            general_boundary: self.general_boundary.to_vec(),
+            interrupted_after: None,
+            budget_exceeded: None,
+            is_approximate: false,
+        }
+    }
+
+    /// Whether the training examples seen so far identify a single hypothesis
+    pub fn is_converged(&self) -> bool {
+        !self.general_boundary.is_empty()
+            && self
+                .general_boundary
+                .iter()
+                .all(|hypothesis| hypothesis == self.specific_boundary)
+    }
+
+    /// Whether no hypothesis is consistent with the training examples seen so far, e.g. because
+    /// they contain contradicting labels
+    pub fn is_collapsed(&self) -> bool {
+        self.general_boundary.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{enjoysport_examples, DATASET_METADATA};
+    use crate::Solver;
+
+    #[test]
+    fn test_incremental_matches_solver() {
+        let mut version_space = VersionSpace::new(&DATASET_METADATA);
+        for example in enjoysport_examples() {
+            version_space.add_example(example);
+        }
+
+        let incremental = version_space.boundaries();
+        let batch = Solver::new(enjoysport_examples(), &DATASET_METADATA).solve();
+
+        assert_eq!(incremental.specific_boundary, batch.specific_boundary);
+        assert_eq!(incremental.general_boundary, batch.general_boundary);
+    }
+
+    #[test]
+    fn test_converges() {
+        let mut version_space = VersionSpace::new(&DATASET_METADATA);
+        for example in enjoysport_examples() {
+            version_space.add_example(example);
+        }
+        assert!(!version_space.is_converged());
+
+        for record in [
+            "Sunny,Warm,Normal,Weak,Cool,Change,true",
+            "Sunny,Cold,High,Weak,Cool,Same,false",
+            "Rainy,Warm,High,Strong,Warm,Same,false",
+        ] {
+            let example = TrainingExample::from_str(record, &DATASET_METADATA).unwrap();
+            version_space.add_example(example);
+        }
+
+        assert!(version_space.is_converged());
+        assert_eq!(
+            version_space.boundaries().general_boundary,
+            vec![Hypothesis::from_str("Sunny,Warm,?,?,?,?", &DATASET_METADATA).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_collapses_on_contradiction() {
+        let mut version_space = VersionSpace::new(&DATASET_METADATA);
+        for is_positive in [true, false] {
+            let mut example = TrainingExample::from_str(
+                "Sunny,Warm,Normal,Weak,Cool,Change,true",
+                &DATASET_METADATA,
+            )
+            .unwrap();
+            example.is_positive = is_positive;
+            version_space.add_example(example);
+        }
+
+        assert!(version_space.is_collapsed());
+        assert!(!version_space.is_converged());
+    }
+}
@@ -0,0 +1,56 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use crate::reader::DatasetMetadata;
+use crate::TrainingExample;
+
+/// Writes training examples in the format read by [`crate::DatasetReader`], using the value names
+/// and wildcard strings from the dataset metadata.
+pub struct DatasetWriter<'a> {
+    writer: csv::Writer<Box<dyn Write>>,
+    metadata: &'a DatasetMetadata,
+}
+
+impl<'a> DatasetWriter<'a> {
+    /// Create a new dataset file, writing `headers` as its first row. The headers name the
+    /// attribute columns followed by the label column.
+    pub fn new<P: AsRef<Path>>(
+        dataset_path: P,
+        metadata: &'a DatasetMetadata,
+        headers: &[String],
+    ) -> Result<Self, csv::Error> {
+        Self::from_writer(File::create(dataset_path)?, metadata, headers)
+    }
+
+    /// Write the dataset to any destination, such as a pipe
+    pub fn from_writer<W: Write + 'static>(
+        writer: W,
+        metadata: &'a DatasetMetadata,
+        headers: &[String],
+    ) -> Result<Self, csv::Error> {
+        let writer: Box<dyn Write> = Box::new(writer);
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(metadata.delimiter.try_into().unwrap())
+            .from_writer(writer);
+
+        writer.write_record(headers)?;
+
+        Ok(Self { writer, metadata })
+    }
+
+    /// Generic headers for datasets whose original column names aren't known
+    pub fn default_headers(metadata: &DatasetMetadata) -> Vec<String> {
+        (0..metadata.columns.len())
+            .map(|index| format!("attribute_{index}"))
+            .chain(std::iter::once("label".to_string()))
+            .collect()
+    }
+
+    pub fn write(&mut self, example: &TrainingExample) -> Result<(), csv::Error> {
+        self.writer
+            .write_record(example.to_dataset_vec(self.metadata))?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
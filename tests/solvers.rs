@@ -0,0 +1,96 @@
+//! Differential tests checking that `Solver`, `ConcurrentSolver` and the on-disk solver learn
+//! identical boundaries, whatever the dataset and however many threads the concurrent solver uses
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ccelm::{random_dataset, verify_solvers, DatasetMetadata, DatasetReader, TrainingExample};
+
+const THREAD_COUNTS: [usize; 3] = [1, 2, 4];
+
+/// Only the first few examples of each included dataset are learned, as the full Boundaries
+/// datasets take too long to learn in a debug build
+const N_EXAMPLES: usize = 6;
+
+/// The directories in `data/` that hold a dataset without metadata, so it can't be learned.
+/// `data/boundaries` is the combined Boundaries dataset, which the other `data/boundaries-*`
+/// directories split up by language family.
+const DATASETS_WITHOUT_METADATA: [&str; 1] = ["boundaries"];
+
+/// The directories in `data/` that hold both a dataset and its metadata. Fails on any directory
+/// without metadata that isn't listed in [`DATASETS_WITHOUT_METADATA`].
+fn included_datasets() -> Vec<(PathBuf, PathBuf)> {
+    let mut datasets = std::fs::read_dir("./data")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter_map(|directory| {
+            let metadata = directory.join("metadata.yaml");
+            let dataset = std::fs::read_dir(&directory)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .find(|path| path.file_stem().is_some_and(|stem| stem == "dataset"))?;
+
+            if metadata.exists() {
+                return Some((dataset, metadata));
+            }
+
+            assert!(
+                DATASETS_WITHOUT_METADATA.iter().any(|name| directory
+                    .file_name()
+                    .is_some_and(|file_name| file_name == *name)),
+                "{} has no metadata.yaml",
+                directory.display()
+            );
+            eprintln!("Skipping {}: it has no metadata", dataset.display());
+            None
+        })
+        .collect::<Vec<_>>();
+    datasets.sort();
+    datasets
+}
+
+fn load(
+    dataset: &Path,
+    metadata: &Path,
+) -> Result<(Vec<TrainingExample>, DatasetMetadata), Box<dyn Error>> {
+    let metadata: DatasetMetadata = serde_yaml::from_reader(std::fs::File::open(metadata)?)?;
+    let training_examples = DatasetReader::new(dataset, metadata.clone())?
+        .take(N_EXAMPLES)
+        .collect::<Result<Vec<TrainingExample>, _>>()?;
+
+    Ok((training_examples, metadata))
+}
+
+#[test]
+fn test_solvers_agree_on_included_datasets() {
+    let datasets = included_datasets();
+    assert!(!datasets.is_empty());
+
+    for (dataset, metadata) in datasets {
+        let (training_examples, metadata) = match load(&dataset, &metadata) {
+            Ok(loaded) => loaded,
+            Err(err) => panic!("Failed to load {}: {err}", dataset.display()),
+        };
+
+        if let Err(err) = verify_solvers(&training_examples, &Arc::new(metadata), &THREAD_COUNTS) {
+            panic!("{}: {err}", dataset.display());
+        }
+    }
+}
+
+#[test]
+fn test_solvers_agree_on_random_datasets() {
+    for (n_attributes, n_values) in [(4, 2), (6, 3), (8, 3)] {
+        for seed in 0..20 {
+            let (metadata, training_examples) =
+                random_dataset(n_attributes, n_values, 20, seed).unwrap();
+
+            if let Err(err) =
+                verify_solvers(&training_examples, &Arc::new(metadata), &THREAD_COUNTS)
+            {
+                panic!("{n_attributes} attributes of {n_values} values, seed {seed}: {err}");
+            }
+        }
+    }
+}